@@ -0,0 +1,76 @@
+//! Model for the sending limits and usage of the MailGun account.
+//!
+//! Full API documentation: [https://documentation.mailgun.com/en/latest/api-accounts.html](https://documentation.mailgun.com/en/latest/api-accounts.html)
+//!
+//! Senders approaching the limit of their plan can check their usage before sending, and
+//! throttle accordingly. Note that the Accounts API lives under the `/v5` base path, rather than
+//! `/v3` like most of the other APIs.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "blocking")] {
+//! use mailgun_sdk::Client as MailGunClient;
+//!
+//! let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+//!
+//! let usage = client.get_account_usage().unwrap();
+//!
+//! if usage.remaining() < 100 {
+//!     println!("{} of {} messages sent this period ({})", usage.used, usage.limit, usage.period);
+//! }
+//! # }
+//! ```
+
+#[cfg(feature = "blocking")]
+use crate::error;
+use serde::Deserialize;
+
+/// Sending limit of the account, and how much of it has been used in the current period.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct AccountUsage {
+    /// Number of messages the account may send per period.
+    pub limit: u64,
+
+    /// Number of messages sent so far in the current period.
+    #[serde(alias = "current")]
+    pub used: u64,
+
+    /// Length of the period the limit applies to, such as `1m` for one month.
+    pub period: String,
+}
+
+impl AccountUsage {
+    /// Number of messages that can still be sent in the current period.
+    pub fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.used)
+    }
+}
+
+/// Get the sending limit and usage of the account of an existing
+/// [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn get_account_usage_with_client(client: &crate::Client) -> Result<AccountUsage, error::Error<'static>> {
+    let request = client.request(reqwest::Method::GET, &client.url_for("/v5/accounts/limit/custom/monthly"));
+
+    client.send_request(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_usage_deserialize() {
+        let body = r#"{ "limit": 10000, "current": 9950, "period": "1m" }"#;
+
+        let usage = serde_json::from_str::<AccountUsage>(body).unwrap();
+
+        assert_eq!(AccountUsage { limit: 10000, used: 9950, period: String::from("1m") }, usage);
+        assert_eq!(50, usage.remaining());
+
+        let usage = AccountUsage { used: 10050, ..usage };
+
+        assert_eq!(0, usage.remaining());
+    }
+}
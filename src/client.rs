@@ -1,9 +1,39 @@
+#[cfg(feature = "blocking")]
+use crate::account;
+#[cfg(feature = "blocking")]
+use crate::credential;
+#[cfg(feature = "blocking")]
+use crate::domain;
 use crate::error;
+use crate::events;
+#[cfg(feature = "blocking")]
+use crate::inbox_placement;
+use crate::instrument::RequestSpan;
 use crate::message;
+#[cfg(any(test, feature = "test-util"))]
+use crate::mock::{CapturedRequest, MockTransport};
+#[cfg(feature = "blocking")]
+use crate::paging::PagedIterator;
+use crate::rate_limit::RateLimiter;
+#[cfg(feature = "blocking")]
+use crate::subaccount;
+#[cfg(feature = "blocking")]
+use crate::suppression;
+#[cfg(feature = "blocking")]
+use crate::webhook;
+use serde::Deserialize;
+#[cfg(feature = "blocking")]
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Utility for interacting with the MailGun API.
 ///
-/// You can create a `Client` via the `new` method.
+/// You can create a `Client` via the `new` method, or with a [`ClientBuilder`](struct.ClientBuilder.html)
+/// to change its options.
 ///
 /// ### Example
 ///
@@ -13,11 +43,68 @@ use crate::message;
 ///
 /// let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
 /// ```
-#[derive(Debug)]
+///
+/// ### Thread Safety
+///
+/// `Client` is `Send + Sync`, and cloning it is cheap: clones share the same connection pool
+/// and request logger. The API key and domain can be borrowed or owned, so a client created
+/// from `String`s is a `Client<'static>` that can be stored in shared application state.
+///
+/// ```rust
+/// use mailgun_sdk::Client as MailGunClient;
+/// use std::thread;
+///
+/// let api_key = String::from("YOUR_API_KEY");
+/// let client = MailGunClient::new(api_key, String::from("YOUR_DOMAIN.com"));
+///
+/// let handle = {
+///     let client = client.clone();
+///     thread::spawn(move || client.domain().to_string())
+/// };
+///
+/// assert_eq!("YOUR_DOMAIN.com", handle.join().unwrap());
+/// ```
+#[derive(Clone)]
 pub struct Client<'a> {
-    api_key: &'a str,
-    client: reqwest::Client,
-    domain: &'a str,
+    auth: Auth<'a>,
+    #[cfg(feature = "blocking")]
+    client: reqwest::blocking::Client,
+    #[cfg(feature = "async")]
+    async_client: reqwest::Client,
+    domain: Cow<'a, str>,
+    max_message_size: u64,
+    region: Region,
+    rate_limiter: Option<RateLimiter>,
+    request_logger: Option<Arc<RequestLogger>>,
+    #[cfg(any(test, feature = "test-util"))]
+    transport: Option<MockTransport>,
+}
+
+/// Username sent with the API key in the basic authentication of requests.
+const DEFAULT_USERNAME: &str = "api";
+
+/// Callback invoked with the details of each request before it is sent.
+pub type RequestLogger = dyn Fn(&RequestInfo) + Send + Sync;
+
+impl<'a> fmt::Debug for Client<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Client");
+
+        debug.field("auth", &self.auth);
+        #[cfg(feature = "blocking")]
+        debug.field("client", &self.client);
+
+        debug
+            .field("domain", &self.domain)
+            .field("max_message_size", &self.max_message_size)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("region", &self.region)
+            .field("request_logger", &self.request_logger.as_ref().map(|_| "Fn(&RequestInfo)"));
+        #[cfg(any(test, feature = "test-util"))]
+        debug.field("transport", &self.transport);
+
+        debug.finish()
+    }
 }
 
 impl<'a> Client<'a> {
@@ -27,55 +114,1969 @@ impl<'a> Client<'a> {
     /// MailGun Control Panel.
     ///
     /// **Important**: Make sure you keep your API key secret.
+    ///
+    /// Panics if the HTTP client cannot be initialized, like `reqwest::blocking::Client::new`.
     #[allow(dead_code)]
-    pub fn new(api_key: &'a str, domain: &'a str) -> Client<'a> {
-        Client {
-            api_key,
-            client: reqwest::Client::new(),
-            domain,
-        }
+    pub fn new<K, D>(api_key: K, domain: D) -> Client<'a>
+    where
+        K: Into<Cow<'a, str>>,
+        D: Into<Cow<'a, str>>,
+    {
+        ClientBuilder::new(api_key, domain)
+            .build()
+            .expect("Unable to initialize the HTTP client")
+    }
+
+    /// Create a new MailGun client from the environment. See
+    /// [`ClientBuilder::from_env`](struct.ClientBuilder.html#method.from_env) for the variables
+    /// that are read.
+    pub fn from_env() -> Result<Client<'static>, error::Error<'static>> {
+        ClientBuilder::from_env()?.build()
+    }
+
+    /// Get the API key, or the token when the client uses bearer authentication.
+    ///
+    /// The key is borrowed from the client, which may own it, rather than returned as the
+    /// `&'a str` passed to [`new`](#method.new).
+    pub fn api_key(&self) -> &str {
+        self.auth.secret()
     }
 
-    /// Get the API key.
-    pub fn api_key(&self) -> &'a str {
-        self.api_key
+    /// Get how the client authenticates requests.
+    pub fn auth(&self) -> &Auth<'a> {
+        &self.auth
     }
 
     /// Get the web client.
-    pub fn client(&'a self) -> &'a reqwest::Client {
+    #[cfg(feature = "blocking")]
+    pub fn client(&self) -> &reqwest::blocking::Client {
         &self.client
     }
 
+    /// Get the username sent with the API key, or `None` when the client uses bearer
+    /// authentication. Defaults to `api`.
+    pub fn username(&self) -> Option<&str> {
+        match &self.auth {
+            Auth::Basic { username, .. } => Some(username),
+            Auth::Bearer { .. } => None,
+        }
+    }
+
+    /// Add the credentials of the client to `request`.
+    ///
+    /// Useful to authenticate requests built with [`client`](#method.client), such as for
+    /// endpoints the library does not cover.
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// use mailgun_sdk::Client as MailGunClient;
+    ///
+    /// let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+    ///
+    /// let request = client.client().get("https://api.mailgun.net/v4/domains");
+    /// let response = client.apply_auth(request).send().unwrap();
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn apply_auth(&self, request: reqwest::blocking::RequestBuilder) -> reqwest::blocking::RequestBuilder {
+        match &self.auth {
+            Auth::Basic { username, api_key } => request.basic_auth(username, Some(api_key)),
+            Auth::Bearer { token } => request.bearer_auth(token),
+        }
+    }
+
+    /// Add the credentials of the client to `request`, with `username` instead of the
+    /// username of the client. For endpoints that expect a different username.
+    ///
+    /// The username is ignored with [`Auth::Bearer`](enum.Auth.html#variant.Bearer).
+    #[cfg(feature = "blocking")]
+    pub fn apply_auth_as(&self, request: reqwest::blocking::RequestBuilder, username: &str) -> reqwest::blocking::RequestBuilder {
+        match &self.auth {
+            Auth::Basic { username: _, api_key } => request.basic_auth(username, Some(api_key)),
+            Auth::Bearer { token } => request.bearer_auth(token),
+        }
+    }
+
     /// Get the domain.
-    pub fn domain(&self) -> &'a str {
-        self.domain
+    ///
+    /// The domain is borrowed from the client, which may own it, rather than returned as the
+    /// `&'a str` passed to [`new`](#method.new).
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// Get the largest message the client sends, in bytes. Defaults to
+    /// [`MAX_MESSAGE_SIZE`](message/constant.MAX_MESSAGE_SIZE.html).
+    pub fn max_message_size(&self) -> u64 {
+        self.max_message_size
+    }
+
+    /// Get the region the client sends requests to.
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    /// Get the rate limiter pacing the requests of the client, if one is set with
+    /// [`ClientBuilder::rate_limit`](struct.ClientBuilder.html#method.rate_limit).
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Get the base URL of the API, based on the region.
+    pub fn base_url(&self) -> &'static str {
+        self.region.base_url()
+    }
+
+    /// Get the full URL of the API endpoint at `path`, such as `/v3/domains`, in the region of
+    /// the client. Every API method builds its URL with this, so requests never mix regions.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// use mailgun_sdk::{ClientBuilder, Region};
+    ///
+    /// let client = ClientBuilder::new("YOUR_API_KEY", "YOUR_DOMAIN.com")
+    ///     .region(Region::Eu)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!("https://api.eu.mailgun.net/v3/domains", client.url_for("/v3/domains"));
+    /// ```
+    pub fn url_for(&self, path: &str) -> String {
+        if path.starts_with('/') {
+            format!("{}{}", self.base_url(), path)
+        } else {
+            format!("{}/{}", self.base_url(), path)
+        }
+    }
+
+    /// Set a callback that is invoked with the method, URL, and body of each request before it
+    /// is sent. Useful for debugging integration issues, such as requests sent to the wrong
+    /// region, since the URL includes the base URL and domain actually used.
+    ///
+    /// The API key is never included in the [`RequestInfo`](struct.RequestInfo.html). Request
+    /// bodies are currently only captured when sending messages.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// use mailgun_sdk::Client as MailGunClient;
+    ///
+    /// let mut client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+    ///
+    /// client.set_request_logger(Box::new(|info| {
+    ///     eprintln!("{} {}", info.method, info.url);
+    /// }));
+    /// ```
+    pub fn set_request_logger(&mut self, request_logger: Box<RequestLogger>) {
+        self.request_logger = Some(Arc::from(request_logger));
     }
 }
 
-// Methods for the Methods API.
-impl<'a> Client<'a> {
-    /// Send a message to MailGun.
+/// Facilitates creating a [`Client`](struct.Client.html) with non-default options.
+///
+/// ### Example
+///
+/// ```rust
+/// use mailgun_sdk::{ClientBuilder, Region};
+/// use std::time::Duration;
+///
+/// let client = ClientBuilder::new("YOUR_API_KEY", "YOUR_DOMAIN.com")
+///     .region(Region::Eu)
+///     .connect_timeout(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(120))
+///     .user_agent("my-app/1.0")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!("https://api.eu.mailgun.net", client.base_url());
+/// ```
+pub struct ClientBuilder<'a> {
+    auth: Auth<'a>,
+    connect_timeout: Option<Duration>,
+    domain: Cow<'a, str>,
+    max_message_size: u64,
+    proxy: Option<reqwest::Proxy>,
+    rate_limit: Option<u32>,
+    redirect: RedirectPolicy,
+    region: Region,
+    request_logger: Option<Box<RequestLogger>>,
+    timeout: Option<Duration>,
+    #[cfg(any(test, feature = "test-util"))]
+    transport: Option<MockTransport>,
+    user_agent: Option<String>,
+}
+
+impl<'a> fmt::Debug for ClientBuilder<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("ClientBuilder");
+
+        debug
+            .field("auth", &self.auth)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("domain", &self.domain)
+            .field("max_message_size", &self.max_message_size)
+            .field("proxy", &self.proxy)
+            .field("rate_limit", &self.rate_limit)
+            .field("redirect", &self.redirect)
+            .field("region", &self.region)
+            .field("request_logger", &self.request_logger.as_ref().map(|_| "Fn(&RequestInfo)"))
+            .field("timeout", &self.timeout);
+        #[cfg(any(test, feature = "test-util"))]
+        debug.field("transport", &self.transport);
+
+        debug.field("user_agent", &self.user_agent).finish()
+    }
+}
+
+impl<'a> ClientBuilder<'a> {
+    /// Create a new client builder. Requires an `api_key` and `domain`, like
+    /// [`Client::new`](struct.Client.html#method.new).
+    pub fn new<K, D>(api_key: K, domain: D) -> ClientBuilder<'a>
+    where
+        K: Into<Cow<'a, str>>,
+        D: Into<Cow<'a, str>>,
+    {
+        ClientBuilder {
+            auth: Auth::basic(api_key),
+            connect_timeout: None,
+            domain: domain.into(),
+            max_message_size: message::MAX_MESSAGE_SIZE,
+            proxy: None,
+            rate_limit: None,
+            redirect: RedirectPolicy::default(),
+            region: Region::default(),
+            request_logger: None,
+            timeout: None,
+            #[cfg(any(test, feature = "test-util"))]
+            transport: None,
+            user_agent: None,
+        }
+    }
+
+    /// Create a new client builder from the environment.
     ///
-    /// Refer to the [`message`](message) module documentation.
+    /// The following variables are read:
+    ///
+    /// - `MAILGUN_API_KEY`: API key. Required.
+    /// - `MAILGUN_DOMAIN`: sending domain. Required.
+    /// - `MAILGUN_REGION`: `us` or `eu`, case-insensitive. Optional, defaults to `us`.
+    ///
+    /// Returns [`Error::MissingEnv`](enum.Error.html#variant.MissingEnv) if a required variable
+    /// is not set, and [`Error::InvalidEnv`](enum.Error.html#variant.InvalidEnv) if a variable
+    /// has an unsupported value.
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// use mailgun_sdk::ClientBuilder;
+    /// use std::time::Duration;
+    ///
+    /// let client = ClientBuilder::from_env()
+    ///     .unwrap()
+    ///     .timeout(Duration::from_secs(10))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn from_env() -> Result<ClientBuilder<'static>, error::Error<'static>> {
+        ClientBuilder::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Create a new client builder from variables looked up with `var`.
+    fn from_vars<F>(var: F) -> Result<ClientBuilder<'static>, error::Error<'static>>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let required = |name: &str| var(name).ok_or_else(|| error::Error::MissingEnv(name.to_string()));
+
+        let mut builder = ClientBuilder::new(required(ENV_API_KEY)?, required(ENV_DOMAIN)?);
+
+        if let Some(region) = var(ENV_REGION) {
+            builder.region = match region.to_lowercase().as_str() {
+                "us" => Region::Us,
+                "eu" => Region::Eu,
+                _ => return Err(error::Error::InvalidEnv { name: ENV_REGION.to_string(), value: region }),
+            };
+        }
+
+        Ok(builder)
+    }
+
+    /// How requests are authenticated. Defaults to basic authentication with the API key
+    /// passed to [`new`](#method.new), and the `api` username the MailGun API expects. Set
+    /// [`Auth::Basic`](enum.Auth.html#variant.Basic) to send another username.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// use mailgun_sdk::{Auth, ClientBuilder};
+    ///
+    /// let client = ClientBuilder::new("", "YOUR_DOMAIN.com")
+    ///     .auth(Auth::Bearer { token: "YOUR_TOKEN".into() })
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!("YOUR_TOKEN", client.api_key());
+    /// ```
+    pub fn auth(mut self, auth: Auth<'a>) -> ClientBuilder<'a> {
+        self.auth = auth;
+
+        self
+    }
+
+    /// Timeout for establishing a connection to MailGun, including the TLS handshake. No
+    /// connect timeout is set by default.
+    ///
+    /// This only bounds the connection phase. The overall [`timeout`](#method.timeout) still
+    /// applies to the whole request, including connecting, so it should be the longer of the
+    /// two. Exceeding either returns [`Error::Timeout`](enum.Error.html#variant.Timeout) or
+    /// [`Error::Connect`](enum.Error.html#variant.Connect).
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> ClientBuilder<'a> {
+        self.connect_timeout = Some(connect_timeout);
+
+        self
+    }
+
+    /// Largest message to send, in bytes. Defaults to
+    /// [`MAX_MESSAGE_SIZE`](message/constant.MAX_MESSAGE_SIZE.html), the limit of most MailGun
+    /// plans. Raise it for plans that accept larger messages.
+    ///
+    /// Sending a larger message returns [`Error::MessageTooLarge`](enum.Error.html#variant.MessageTooLarge)
+    /// without sending a request.
+    pub fn max_message_size(mut self, max_message_size: u64) -> ClientBuilder<'a> {
+        self.max_message_size = max_message_size;
+
+        self
+    }
+
+    /// Send requests through a proxy.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> ClientBuilder<'a> {
+        self.proxy = Some(proxy);
+
+        self
+    }
+
+    /// Pace requests to at most `per_second` requests per second, with a
+    /// [`RateLimiter`](struct.RateLimiter.html) shared by every clone of the client. Disabled
+    /// by default, and `0` disables it again.
+    ///
+    /// Pacing keeps high-volume senders below the rate limits of MailGun, instead of reacting
+    /// to `429 Too Many Requests` responses. Requests wait for their turn before they are sent:
+    /// blocking requests block the current thread, while asynchronous requests yield to the
+    /// runtime until a request is allowed.
+    pub fn rate_limit(mut self, per_second: u32) -> ClientBuilder<'a> {
+        self.rate_limit = Some(per_second).filter(|per_second| *per_second > 0);
+
+        self
+    }
+
+    /// How redirects are followed. Defaults to [`RedirectPolicy::None`](enum.RedirectPolicy.html#variant.None),
+    /// since MailGun does not redirect legitimate API responses.
+    ///
+    /// Redirects that are not followed are returned as
+    /// [`Error::UnexpectedRedirect`](enum.Error.html#variant.UnexpectedRedirect).
+    pub fn redirect(mut self, redirect: RedirectPolicy) -> ClientBuilder<'a> {
+        self.redirect = redirect;
+
+        self
+    }
+
+    /// Region of the MailGun account. Defaults to [`Region::Us`](enum.Region.html#variant.Us).
+    pub fn region(mut self, region: Region) -> ClientBuilder<'a> {
+        self.region = region;
+
+        self
+    }
+
+    /// Set a callback that is invoked with the details of each request. See
+    /// [`Client::set_request_logger`](struct.Client.html#method.set_request_logger).
+    pub fn request_logger(mut self, request_logger: Box<RequestLogger>) -> ClientBuilder<'a> {
+        self.request_logger = Some(request_logger);
+
+        self
+    }
+
+    /// Timeout of each request, from connecting until the response body has been read.
     ///
-    /// Panics if no body is set. Make sure you set either the [`text`](message/struct.MessageBuilder.html#method.text)
-    /// or [`html`](message/struct.MessageBuilder.html#method.html) field of the message before trying to
-    /// send it.
-    pub fn send_message(&self, message: &'a message::Message) -> Result<message::SendMessageResponse, error::Error> {
-        message::send_message_with_client(&self, message)
+    /// Defaults to 30 seconds for blocking requests, and no timeout for asynchronous requests.
+    /// Raise it when uploading large attachments over slow connections, and set a shorter
+    /// [`connect_timeout`](#method.connect_timeout) to still fail fast when MailGun cannot be
+    /// reached.
+    pub fn timeout(mut self, timeout: Duration) -> ClientBuilder<'a> {
+        self.timeout = Some(timeout);
+
+        self
+    }
+
+    /// Send requests to `transport` instead of MailGun, so they can be asserted on in tests.
+    /// Requires the `test-util` feature.
+    ///
+    /// Refer to the [`mock`](mock) module documentation.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn transport(mut self, transport: MockTransport) -> ClientBuilder<'a> {
+        self.transport = Some(transport);
+
+        self
+    }
+
+    /// Value of the `User-Agent` header sent with each request.
+    pub fn user_agent(mut self, user_agent: &str) -> ClientBuilder<'a> {
+        self.user_agent = Some(user_agent.to_string());
+
+        self
+    }
+
+    /// Create the client.
+    ///
+    /// Fails if the HTTP client cannot be initialized, such as when the TLS backend is
+    /// unavailable.
+    pub fn build(self) -> Result<Client<'a>, error::Error<'static>> {
+        #[cfg(feature = "blocking")]
+        let mut client = reqwest::blocking::Client::builder();
+        #[cfg(feature = "async")]
+        let mut async_client = reqwest::Client::builder();
+
+        if let Some(proxy) = &self.proxy {
+            #[cfg(feature = "blocking")]
+            {
+                client = client.proxy(proxy.clone());
+            }
+            #[cfg(feature = "async")]
+            {
+                async_client = async_client.proxy(proxy.clone());
+            }
+        }
+
+        #[cfg(feature = "blocking")]
+        {
+            client = client.redirect(self.redirect.to_policy());
+        }
+        #[cfg(feature = "async")]
+        {
+            async_client = async_client.redirect(self.redirect.to_policy());
+        }
+
+        if let Some(connect_timeout) = self.connect_timeout {
+            #[cfg(feature = "blocking")]
+            {
+                client = client.connect_timeout(connect_timeout);
+            }
+            #[cfg(feature = "async")]
+            {
+                async_client = async_client.connect_timeout(connect_timeout);
+            }
+        }
+
+        if let Some(timeout) = self.timeout {
+            #[cfg(feature = "blocking")]
+            {
+                client = client.timeout(timeout);
+            }
+            #[cfg(feature = "async")]
+            {
+                async_client = async_client.timeout(timeout);
+            }
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            #[cfg(feature = "blocking")]
+            {
+                client = client.user_agent(user_agent.as_str());
+            }
+            #[cfg(feature = "async")]
+            {
+                async_client = async_client.user_agent(user_agent.as_str());
+            }
+        }
+
+        Ok(Client {
+            auth: self.auth,
+            #[cfg(feature = "blocking")]
+            client: client.build()?,
+            #[cfg(feature = "async")]
+            async_client: async_client.build()?,
+            domain: self.domain,
+            max_message_size: self.max_message_size,
+            rate_limiter: self.rate_limit.map(RateLimiter::new),
+            region: self.region,
+            request_logger: self.request_logger.map(Arc::from),
+            #[cfg(any(test, feature = "test-util"))]
+            transport: self.transport,
+        })
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Name of the environment variable holding the API key.
+const ENV_API_KEY: &str = "MAILGUN_API_KEY";
 
-    #[test]
-    fn client_new() {
-        let client = Client::new("api_key", "domain");
+/// Name of the environment variable holding the domain.
+const ENV_DOMAIN: &str = "MAILGUN_DOMAIN";
 
-        assert_eq!("api_key", client.api_key);
-        assert_eq!("domain", client.domain);
+/// Name of the environment variable holding the region.
+const ENV_REGION: &str = "MAILGUN_REGION";
+
+/// Policy for following the redirects of API responses.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RedirectPolicy {
+    /// Never follow redirects.
+    #[default]
+    None,
+
+    /// Follow up to the given number of redirects, as long as they stay on the host of the
+    /// original request.
+    SameHost(usize),
+
+    /// Follow up to the given number of redirects to any host.
+    Limited(usize),
+}
+
+impl RedirectPolicy {
+    /// Build the equivalent `reqwest` redirect policy.
+    fn to_policy(self) -> reqwest::redirect::Policy {
+        match self {
+            RedirectPolicy::None => reqwest::redirect::Policy::none(),
+            RedirectPolicy::Limited(max) => reqwest::redirect::Policy::limited(max),
+            RedirectPolicy::SameHost(max) => reqwest::redirect::Policy::custom(move |attempt| {
+                let same_host = attempt.previous()
+                    .first()
+                    .is_some_and(|first| first.host_str() == attempt.url().host_str());
+
+                if !same_host {
+                    attempt.stop()
+                } else if attempt.previous().len() > max {
+                    attempt.error("too many redirects")
+                } else {
+                    attempt.follow()
+                }
+            }),
+        }
+    }
+}
+
+/// Return an error if a response is a redirect that was not followed.
+pub(crate) fn check_redirect(status: reqwest::StatusCode, headers: &reqwest::header::HeaderMap) -> Result<(), error::Error<'static>> {
+    if !status.is_redirection() || status == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(());
+    }
+
+    let location = headers
+        .get(reqwest::header::LOCATION)
+        .and_then(|location| location.to_str().ok())
+        .map(String::from);
+
+    Err(error::Error::UnexpectedRedirect { status: status.as_u16(), location })
+}
+
+/// Maximum number of characters of a non-JSON body kept in an
+/// [`Error::UnexpectedResponse`](enum.Error.html#variant.UnexpectedResponse) or
+/// [`Error::ApiError`](enum.Error.html#variant.ApiError).
+const BODY_PREVIEW_LENGTH: usize = 200;
+
+/// Return the start of `body`, for errors about responses that are not JSON.
+pub(crate) fn body_preview(body: &str) -> String {
+    match body.char_indices().nth(BODY_PREVIEW_LENGTH) {
+        Some((end, _)) => format!("{}...", &body[..end]),
+        None => body.to_string(),
+    }
+}
+
+/// Return an error if a successful response is not JSON, such as an HTML page sent by a proxy.
+///
+/// Responses without a `Content-Type`, and bodies that parse as JSON despite their content
+/// type, are accepted. Unsuccessful responses are left to the response parsers, which map
+/// their status first, so a "not found" is reported as such whatever its body.
+pub(crate) fn check_content_type(status: reqwest::StatusCode, headers: &reqwest::header::HeaderMap, body: &str) -> Result<(), error::Error<'static>> {
+    if !status.is_success() {
+        return Ok(());
+    }
+
+    let content_type = match headers.get(reqwest::header::CONTENT_TYPE).and_then(|value| value.to_str().ok()) {
+        Some(content_type) => content_type,
+        None => return Ok(()),
+    };
+
+    if content_type.contains("json") || serde_json::from_str::<serde::de::IgnoredAny>(body).is_ok() {
+        return Ok(());
+    }
+
+    Err(error::Error::UnexpectedResponse {
+        status: status.as_u16(),
+        content_type: content_type.to_string(),
+        body_preview: body_preview(body),
+    })
+}
+
+/// Credentials used to authenticate requests.
+///
+/// The `Debug` output only shows the kind of authentication, and redacts the API key or token.
+#[derive(Clone, Eq, PartialEq)]
+pub enum Auth<'a> {
+    /// HTTP basic authentication with the API key as the password. Used by most of the API,
+    /// which expects the `api` username.
+    Basic {
+        username: Cow<'a, str>,
+        api_key: Cow<'a, str>,
+    },
+
+    /// Bearer token authentication, for APIs that accept tokens.
+    Bearer {
+        token: Cow<'a, str>,
+    },
+}
+
+impl<'a> fmt::Debug for Auth<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Auth::Basic { username, .. } => f.debug_struct("Basic").field("username", username).field("api_key", &format_args!("{}", REDACTED)).finish(),
+            Auth::Bearer { .. } => f.debug_struct("Bearer").field("token", &format_args!("{}", REDACTED)).finish(),
+        }
+    }
+}
+
+impl<'a> Auth<'a> {
+    /// Create basic authentication with `api_key` and the `api` username.
+    pub fn basic<K>(api_key: K) -> Auth<'a>
+    where
+        K: Into<Cow<'a, str>>,
+    {
+        Auth::Basic { username: Cow::Borrowed(DEFAULT_USERNAME), api_key: api_key.into() }
+    }
+
+    /// Get the scheme of the `Authorization` header, `Basic` or `Bearer`.
+    pub(crate) fn scheme(&self) -> &'static str {
+        match self {
+            Auth::Basic { .. } => "Basic",
+            Auth::Bearer { .. } => "Bearer",
+        }
+    }
+
+    /// Get the API key or token.
+    pub(crate) fn secret(&self) -> &str {
+        match self {
+            Auth::Basic { username: _, api_key } => api_key,
+            Auth::Bearer { token } => token,
+        }
+    }
+
+    /// Copy the credentials into a value that does not borrow.
+    #[cfg(feature = "async")]
+    pub(crate) fn into_owned(self) -> Auth<'static> {
+        match self {
+            Auth::Basic { username, api_key } => Auth::Basic {
+                username: Cow::Owned(username.into_owned()),
+                api_key: Cow::Owned(api_key.into_owned()),
+            },
+            Auth::Bearer { token } => Auth::Bearer { token: Cow::Owned(token.into_owned()) },
+        }
+    }
+}
+
+/// Region of a MailGun account, which determines the host API requests are sent to.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Region {
+    /// United States, at `api.mailgun.net`.
+    #[default]
+    Us,
+
+    /// European Union, at `api.eu.mailgun.net`.
+    Eu,
+}
+
+impl Region {
+    /// Base URL of the API in this region.
+    pub fn base_url(self) -> &'static str {
+        match self {
+            Region::Us => "https://api.mailgun.net",
+            Region::Eu => "https://api.eu.mailgun.net",
+        }
+    }
+}
+
+/// Details of a request about to be sent to MailGun, passed to the request logger.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestInfo {
+    /// HTTP method of the request.
+    pub method: String,
+
+    /// Full URL of the request.
+    pub url: String,
+
+    /// Body of the request, when known.
+    pub body: Option<String>,
+}
+
+impl RequestInfo {
+    /// Create the request details, redacting any occurrence of the API key.
+    fn new(method: &reqwest::Method, url: &str, body: Option<&str>, api_key: &str) -> RequestInfo {
+        let redact = |value: &str| {
+            if api_key.is_empty() {
+                value.to_string()
+            } else {
+                value.replace(api_key, REDACTED)
+            }
+        };
+
+        RequestInfo {
+            method: method.to_string(),
+            url: redact(url),
+            body: body.map(redact),
+        }
+    }
+}
+
+/// Placeholder used in place of the API key when logging requests.
+pub(crate) const REDACTED: &str = "[REDACTED]";
+
+// Helpers shared by the API methods.
+impl<'a> Client<'a> {
+    /// Build an authenticated request for a MailGun API endpoint.
+    #[cfg(feature = "blocking")]
+    pub(crate) fn request(&self, method: reqwest::Method, url: &str) -> reqwest::blocking::RequestBuilder {
+        self.apply_auth(self.client.request(method, url))
+    }
+
+    /// Pass the details of a request to the request logger, if one is set.
+    pub(crate) fn log_request(&self, method: &reqwest::Method, url: &str, body: Option<&str>) {
+        if let Some(request_logger) = &self.request_logger {
+            request_logger(&RequestInfo::new(method, url, body, self.api_key()));
+        }
+    }
+
+    /// Return a handle for building asynchronous requests that does not borrow the client.
+    #[cfg(feature = "async")]
+    pub(crate) fn async_requester(&self) -> AsyncRequester {
+        AsyncRequester {
+            auth: self.auth.clone().into_owned(),
+            client: self.async_client.clone(),
+            domain: self.domain.to_string(),
+            rate_limiter: self.rate_limiter.clone(),
+            #[cfg(any(test, feature = "test-util"))]
+            transport: self.transport.clone(),
+        }
+    }
+
+    /// Send a request and deserialize the JSON response.
+    #[cfg(feature = "blocking")]
+    pub(crate) fn send_request<T>(&self, request: reqwest::blocking::RequestBuilder) -> Result<T, error::Error<'static>>
+    where
+        T: DeserializeOwned,
+    {
+        let request = request.build()?;
+
+        self.log_request(request.method(), request.url().as_str(), None);
+
+        let span = RequestSpan::new(request.url().path(), &self.domain);
+
+        let (status, response_text) = self.execute(request, &span)?;
+
+        parse_response(status, response_text)
+    }
+
+    /// Send a built request within `span`, and return the status and body of the response once
+    /// redirects and non-JSON responses are ruled out. Waits for the rate limiter first, if any.
+    ///
+    /// With the `test-util` feature, the request is handed to the mock transport instead, if
+    /// the client has one. A pending [`MockResponse`](mock/struct.MockResponse.html#method.pending)
+    /// fails with a timeout, like a response that never arrives.
+    #[cfg(feature = "blocking")]
+    pub(crate) fn execute(&self, request: reqwest::blocking::Request, span: &RequestSpan) -> Result<(reqwest::StatusCode, String), error::Error<'static>> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire();
+        }
+
+        #[cfg(any(test, feature = "test-util"))]
+        {
+            if let Some(transport) = &self.transport {
+                let body = request.body().and_then(|body| body.as_bytes()).unwrap_or_default();
+                let captured = CapturedRequest::new(request.method(), request.url().as_str(), request.headers(), body);
+
+                let response = transport.respond(captured);
+
+                if response.is_pending() {
+                    return Err(error::Error::Timeout(String::from("the mock response is pending")));
+                }
+
+                let (status, headers, response_text) = response.into_parts();
+                span.record_status(status.as_u16());
+
+                check_redirect(status, &headers)?;
+                check_content_type(status, &headers, &response_text)?;
+
+                return Ok((status, response_text));
+            }
+        }
+
+        let response = span.in_scope(|| self.client.execute(request))?;
+
+        let status = response.status();
+        span.record_status(status.as_u16());
+
+        check_redirect(status, response.headers())?;
+
+        let headers = response.headers().clone();
+        let response_text = response.text()?;
+
+        check_content_type(status, &headers, &response_text)?;
+
+        Ok((status, response_text))
+    }
+}
+
+/// Owned handle for building authenticated asynchronous requests.
+#[cfg(feature = "async")]
+pub(crate) struct AsyncRequester {
+    auth: Auth<'static>,
+    client: reqwest::Client,
+    domain: String,
+    rate_limiter: Option<RateLimiter>,
+    #[cfg(any(test, feature = "test-util"))]
+    transport: Option<MockTransport>,
+}
+
+#[cfg(feature = "async")]
+impl AsyncRequester {
+    /// Build an authenticated request for a MailGun API endpoint.
+    pub(crate) fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        let request = self.client.request(method, url);
+
+        match &self.auth {
+            Auth::Basic { username, api_key } => request.basic_auth(username, Some(api_key)),
+            Auth::Bearer { token } => request.bearer_auth(token),
+        }
+    }
+
+    /// Send a request and deserialize the JSON response.
+    pub(crate) async fn send<T>(&self, request: reqwest::RequestBuilder) -> Result<T, error::Error<'static>>
+    where
+        T: DeserializeOwned,
+    {
+        let request = request.build()?;
+
+        let span = self.span(&request);
+
+        let (status, response_text) = self.execute(request, &span).await?;
+
+        parse_response(status, response_text)
+    }
+
+    /// Start a span for `request`, on behalf of the domain of the client.
+    pub(crate) fn span(&self, request: &reqwest::Request) -> RequestSpan {
+        RequestSpan::new(request.url().path(), &self.domain)
+    }
+
+    /// Send a built request within `span`, and return the status and body of the response once
+    /// redirects and non-JSON responses are ruled out, like `Client::execute`. Waits for the
+    /// rate limiter first, if any, without blocking the thread polling the future.
+    ///
+    /// A pending [`MockResponse`](mock/struct.MockResponse.html#method.pending) of the mock
+    /// transport never resolves, like a response that never arrives.
+    pub(crate) async fn execute(&self, request: reqwest::Request, span: &RequestSpan) -> Result<(reqwest::StatusCode, String), error::Error<'static>> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire_async().await;
+        }
+
+        #[cfg(any(test, feature = "test-util"))]
+        {
+            if let Some(transport) = &self.transport {
+                let body = request.body().and_then(|body| body.as_bytes()).unwrap_or_default();
+                let captured = CapturedRequest::new(request.method(), request.url().as_str(), request.headers(), body);
+
+                let response = transport.respond(captured);
+
+                if response.is_pending() {
+                    std::future::pending::<()>().await;
+                }
+
+                let (status, headers, response_text) = response.into_parts();
+                span.record_status(status.as_u16());
+
+                check_redirect(status, &headers)?;
+                check_content_type(status, &headers, &response_text)?;
+
+                return Ok((status, response_text));
+            }
+        }
+
+        let response = span.instrument(self.client.execute(request)).await?;
+
+        let status = response.status();
+        span.record_status(status.as_u16());
+
+        check_redirect(status, response.headers())?;
+
+        let headers = response.headers().clone();
+        let response_text = response.text().await?;
+
+        check_content_type(status, &headers, &response_text)?;
+
+        Ok((status, response_text))
+    }
+}
+
+/// Deserialize the JSON body of a response to an API request.
+///
+/// Unsuccessful status codes are returned as an [`Error`](enum.Error.html), using the
+/// `message` field of the response body when MailGun provides one, or the start of the body
+/// otherwise.
+pub(crate) fn parse_response<T>(status: reqwest::StatusCode, response_text: String) -> Result<T, error::Error<'static>>
+where
+    T: DeserializeOwned,
+{
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(error::Error::Unauthorized);
+    }
+
+    if status == reqwest::StatusCode::FORBIDDEN {
+        return Err(error::Error::ApiForbiddenError);
+    }
+
+    if !status.is_success() {
+        let message = serde_json::from_str::<ApiErrorResponse>(&response_text)
+            .map(|response| response.message)
+            .unwrap_or_else(|_| body_preview(&response_text));
+
+        return Err(error::Error::ApiError { status: status.as_u16(), message });
+    }
+
+    serde_json::from_str::<T>(&response_text)
+        .map_err(|error| error::Error::Decode(error.to_string()))
+}
+
+/// Error body sent back from MailGun for unsuccessful API requests.
+#[derive(Debug, Deserialize)]
+struct ApiErrorResponse {
+    message: String,
+}
+
+// Methods for calling any API endpoint.
+#[cfg(feature = "blocking")]
+impl<'a> Client<'a> {
+    /// Send a `GET` request to the API endpoint at `path`, such as `/v3/domains`, and
+    /// deserialize the JSON response into `T`.
+    ///
+    /// Useful for endpoints the library does not cover yet. Authentication, the base URL of the
+    /// region, and error responses are handled like the other methods.
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// use mailgun_sdk::Client as MailGunClient;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Domains {
+    ///     total_count: u32,
+    /// }
+    ///
+    /// let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+    ///
+    /// let domains = client.get_json::<Domains>("/v3/domains").unwrap();
+    /// println!("{} domains", domains.total_count);
+    /// ```
+    pub fn get_json<T>(&self, path: &str) -> Result<T, error::Error<'_>>
+    where
+        T: DeserializeOwned,
+    {
+        let request = self.request(reqwest::Method::GET, &self.url_for(path));
+
+        self.send_request(request)
+    }
+
+    /// Send a `POST` request with the form-encoded `params` to the API endpoint at `path`, and
+    /// deserialize the JSON response into `T`. See [`get_json`](#method.get_json).
+    pub fn post_form<T, P>(&self, path: &str, params: &P) -> Result<T, error::Error<'_>>
+    where
+        T: DeserializeOwned,
+        P: Serialize + ?Sized,
+    {
+        let request = self
+            .request(reqwest::Method::POST, &self.url_for(path))
+            .form(params);
+
+        self.send_request(request)
+    }
+}
+
+// Methods for the Messages API.
+impl<'a> Client<'a> {
+    /// Send a message to MailGun.
+    ///
+    /// Refer to the [`message`](message) module documentation.
+    ///
+    /// Returns [`Error::MissingBody`](enum.Error.html#variant.MissingBody) if no body is set. Make sure you set the [`text`](message/struct.MessageBuilder.html#method.text),
+    /// [`html`](message/struct.MessageBuilder.html#method.html), or [`template`](message/struct.MessageBuilder.html#method.template)
+    /// field of the message before trying to send it.
+    #[cfg(feature = "blocking")]
+    pub fn send_message(&self, message: &'a message::Message) -> Result<message::SendMessageResponse, error::Error<'_>> {
+        message::send_message_with_client(self, message)
+    }
+
+    /// Send a message to MailGun, and return the response along with the delivery time the
+    /// message was scheduled for with
+    /// [`option_deliverytime`](message/struct.MessageBuilder.html#method.option_deliverytime),
+    /// if any. See [`message::SendOutcome`](message/struct.SendOutcome.html).
+    #[cfg(feature = "blocking")]
+    pub fn send_message_outcome(&self, message: &'a message::Message) -> Result<message::SendOutcome, error::Error<'_>> {
+        message::send_message_outcome_with_client(self, message)
+    }
+
+    /// Send a message to MailGun from `domain`, instead of the domain the client was created
+    /// with. Useful when a single client sends from multiple domains.
+    ///
+    /// Returns [`Error::MissingBody`](enum.Error.html#variant.MissingBody) if no body is set, like
+    /// [`send_message`](#method.send_message).
+    #[cfg(feature = "blocking")]
+    pub fn send_message_from_domain(&self, domain: &str, message: &'a message::Message) -> Result<message::SendMessageResponse, error::Error<'_>> {
+        message::send_message_from_domain_with_client(self, domain, message)
+    }
+
+    /// Send a message as raw MIME built by the library, encoding each attachment with its
+    /// [`TransferEncoding`](message/enum.TransferEncoding.html). Requires the `send-mime`
+    /// feature. See [`message::send_mime_with_client`](message/fn.send_mime_with_client.html).
+    #[cfg(feature = "send-mime")]
+    pub fn send_mime(&self, message: &'a message::Message) -> Result<message::SendMessageResponse, error::Error<'_>> {
+        message::send_mime_with_client(self, message)
+    }
+
+    /// Send a batch message to the `to` recipients that are not suppressed, reporting which
+    /// recipients were skipped and why.
+    ///
+    /// Invalid addresses and addresses on a suppression list of the domain are left out of the
+    /// message. See [`message::batch_send_with_client`](message/fn.batch_send_with_client.html).
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// use mailgun_sdk::Client as MailGunClient;
+    /// use mailgun_sdk::message::{self, Email, MessageBuilder};
+    ///
+    /// let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+    ///
+    /// let from = Email::from("news@YOUR_DOMAIN.com");
+    /// let to = message::emails(&["recipient1@domain.com", "recipient2@domain.com"]);
+    ///
+    /// let mut builder = MessageBuilder::new("Monthly Newsletter", &from, &to);
+    /// builder.text(Some("This month's news"));
+    ///
+    /// let result = client.batch_send(builder.get_message()).unwrap();
+    ///
+    /// for (email, reason) in &result.skipped {
+    ///     println!("Skipped {}: {:?}", email, reason);
+    /// }
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn batch_send<'m>(&self, message: &message::Message<'m>) -> Result<message::BatchResult<'m>, error::Error<'_>> {
+        message::batch_send_with_client(self, message)
+    }
+
+    /// Send a message to MailGun asynchronously.
+    ///
+    /// Dropping the returned future cancels the in-flight request, even in the middle of
+    /// uploading large attachments. To cancel on a signal, race the future against it.
+    ///
+    /// Returns [`Error::MissingBody`](enum.Error.html#variant.MissingBody) if no body is set, like
+    /// [`send_message`](#method.send_message).
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// use mailgun_sdk::Client as MailGunClient;
+    /// use mailgun_sdk::message::{Email, MessageBuilder};
+    ///
+    /// # async fn run(cancelled: impl std::future::Future<Output = ()> + Unpin) {
+    /// use futures_util::future::{self, Either};
+    ///
+    /// let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+    ///
+    /// let from = Email::new(None, "from@host.com");
+    /// let to = vec![Email::new(None, "to@host.com")];
+    ///
+    /// let mut builder = MessageBuilder::new("Subject Line", &from, &to);
+    /// builder.text(Some("Message body"));
+    ///
+    /// let send = Box::pin(client.send_message_async(builder.get_message()));
+    ///
+    /// match future::select(send, cancelled).await {
+    ///     Either::Left((response, _)) => println!("{:?}", response.unwrap()),
+    ///     Either::Right(_) => println!("Cancelled"),
+    /// };
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn send_message_async(&self, message: &'a message::Message<'_>) -> Result<message::SendMessageResponse, error::Error<'_>> {
+        message::send_message_async_with_client(self, message).await
+    }
+
+    /// Build the request [`send_message`](#method.send_message) would send, without sending
+    /// it. The API key is redacted from the returned request.
+    ///
+    /// Useful for asserting on the URL, headers, and body of a message in tests.
+    ///
+    /// Returns [`Error::MissingBody`](enum.Error.html#variant.MissingBody) if no body is set, like
+    /// [`send_message`](#method.send_message).
+    pub fn send_message_dry_run(&self, message: &'a message::Message) -> Result<message::PreparedRequest, error::Error<'_>> {
+        message::send_message_dry_run_with_client(self, message)
+    }
+
+    /// Get a stored message from its `url`, found in the `storage` field of its events.
+    ///
+    /// Returns [`Error::StoredMessageExpired`](enum.Error.html#variant.StoredMessageExpired)
+    /// once MailGun no longer keeps the message, which happens after 3 days, and
+    /// [`Error::InvalidStorageUrl`](enum.Error.html#variant.InvalidStorageUrl) without sending
+    /// anything if `url` is not on a MailGun API host.
+    #[cfg(feature = "blocking")]
+    pub fn get_stored_message(&self, url: &str) -> Result<message::StoredMessage, error::Error<'_>> {
+        message::get_stored_message_with_client(self, url)
+    }
+
+    /// Get the full raw MIME of a stored message from its `url`, found in the `storage` field of
+    /// its events, for archiving exactly what was sent.
+    ///
+    /// Returns [`Error::StoredMessageExpired`](enum.Error.html#variant.StoredMessageExpired)
+    /// once MailGun no longer keeps the message, which happens after 3 days, and
+    /// [`Error::InvalidStorageUrl`](enum.Error.html#variant.InvalidStorageUrl) without sending
+    /// anything if `url` is not on a MailGun API host.
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// use mailgun_sdk::Client as MailGunClient;
+    /// use mailgun_sdk::Error;
+    ///
+    /// let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+    ///
+    /// match client.get_sent_mime("https://storage.api.mailgun.net/v3/domains/YOUR_DOMAIN.com/messages/KEY") {
+    ///     Ok(mime) => std::fs::write("archive.eml", mime).unwrap(),
+    ///     Err(Error::StoredMessageExpired(url)) => eprintln!("{} is no longer stored", url),
+    ///     Err(error) => eprintln!("{}", error),
+    /// }
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn get_sent_mime(&self, url: &str) -> Result<String, error::Error<'_>> {
+        message::get_sent_mime_with_client(self, url)
+    }
+
+    /// Get the full URL messages from `domain` are sent to, based on the region of the client.
+    ///
+    /// Useful for logging which endpoint a message was sent to. The
+    /// [`RequestInfo`](struct.RequestInfo.html) passed to the request logger also contains
+    /// this URL.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// use mailgun_sdk::{ClientBuilder, Region};
+    ///
+    /// let client = ClientBuilder::new("YOUR_API_KEY", "YOUR_DOMAIN.com")
+    ///     .region(Region::Eu)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     "https://api.eu.mailgun.net/v3/YOUR_DOMAIN.com/messages",
+    ///     client.messages_url(client.domain()),
+    /// );
+    /// ```
+    pub fn messages_url(&self, domain: &str) -> String {
+        self.url_for(&message::messages_path(domain))
+    }
+
+    /// Delete a message scheduled for later delivery, using the storage key of the message.
+    #[cfg(feature = "blocking")]
+    pub fn delete_scheduled_message(&self, storage_key: &str) -> Result<message::DeleteMessageResponse, error::Error<'_>> {
+        message::delete_scheduled_message_with_client(self, storage_key)
+    }
+
+    /// Iterate over the messages scheduled for later delivery from the domain, following the
+    /// pages of results.
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// use mailgun_sdk::Client as MailGunClient;
+    ///
+    /// let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+    ///
+    /// for scheduled in client.list_scheduled_messages() {
+    ///     let scheduled = scheduled.unwrap();
+    ///
+    ///     println!("{} is scheduled for {}", scheduled.storage_key, scheduled.scheduled_for);
+    /// }
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn list_scheduled_messages(&self) -> PagedIterator<'_, message::ScheduledMessage> {
+        message::list_scheduled_messages_with_client(self)
+    }
+
+    /// Move a message scheduled for later delivery to `deliverytime`, by deleting the message
+    /// stored under `storage_key` and sending `message` again. Returns the response for the new
+    /// message.
+    ///
+    /// This is not atomic: if sending fails after the delete, the message is no longer scheduled.
+    /// Refer to [`cancel_and_reschedule_with_client`](message/fn.cancel_and_reschedule_with_client.html).
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// use mailgun_sdk::Client as MailGunClient;
+    /// use mailgun_sdk::message::{self, Email, MessageBuilder};
+    ///
+    /// let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+    ///
+    /// let from = Email::from("from@host.com");
+    /// let to = message::emails(&["to@host.com"]);
+    ///
+    /// let mut builder = MessageBuilder::new("Subject Line", &from, &to);
+    /// builder.text(Some("Message Body"));
+    /// builder.option_deliverytime(Some("Fri, 05 Mar 2021 14:30:00 +0000"));
+    ///
+    /// let response = client.cancel_and_reschedule(
+    ///     "STORAGE_KEY",
+    ///     builder.get_message(),
+    ///     "Sat, 06 Mar 2021 09:00:00 +0000",
+    /// ).unwrap();
+    ///
+    /// println!("{:?}", response.scheduled_for());
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn cancel_and_reschedule(&self, storage_key: &str, message: &message::Message, deliverytime: &str) -> Result<message::SendOutcome, error::Error<'_>> {
+        message::cancel_and_reschedule_with_client(self, storage_key, message, deliverytime)
+    }
+}
+
+// Methods for the Events API.
+impl<'a> Client<'a> {
+    /// Get the first page of events matching `query`.
+    ///
+    /// Refer to the [`events`](events) module documentation.
+    #[cfg(feature = "blocking")]
+    pub fn get_events(&self, query: &events::EventsQuery) -> Result<events::EventsResponse, error::Error<'_>> {
+        events::get_events_with_client(self, query)
+    }
+
+    /// Iterate over all events matching `query`, transparently following the pages of results.
+    ///
+    /// Each page is fetched once the events of the previous page have been consumed.
+    #[cfg(feature = "blocking")]
+    pub fn iter_events(&self, query: &events::EventsQuery) -> PagedIterator<'_, events::Event> {
+        events::iter_events_with_client(self, query)
+    }
+
+    /// Get all events of the message with `message_id`, following the pages of results.
+    ///
+    /// `message_id` may include the angle brackets returned when sending a message.
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// use mailgun_sdk::Client as MailGunClient;
+    ///
+    /// let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+    ///
+    /// for event in client.get_events_for_message("<20210305143000.1.ABCDEF@YOUR_DOMAIN.com>").unwrap() {
+    ///     println!("{}", event.event);
+    /// }
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn get_events_for_message(&self, message_id: &str) -> Result<Vec<events::Event>, error::Error<'_>> {
+        events::get_events_for_message_with_client(self, message_id)
+    }
+
+    /// Collect up to `max` events matching `query`, following the pages of results until
+    /// `max` events are gathered or the last page is reached.
+    ///
+    /// This is the blocking counterpart of [`events_stream`](#method.events_stream), for jobs
+    /// such as daily reports. `max` bounds the memory used by the result.
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// use mailgun_sdk::Client as MailGunClient;
+    /// use mailgun_sdk::events::EventsQuery;
+    ///
+    /// let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+    ///
+    /// let query = EventsQuery {
+    ///     event: Some(String::from("failed")),
+    ///     begin: Some(String::from("Fri, 5 Mar 2021 00:00:00 +0000")),
+    ///     end: Some(String::from("Sat, 6 Mar 2021 00:00:00 +0000")),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let events = client.collect_events(&query, 10_000).unwrap();
+    /// println!("{} failures", events.len());
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn collect_events(&self, query: &events::EventsQuery, max: usize) -> Result<Vec<events::Event>, error::Error<'_>> {
+        events::collect_events_with_client(self, query, max)
+    }
+
+    /// Get the page of events at `url`, taken from the `paging` of a previous page.
+    #[cfg(feature = "blocking")]
+    pub fn get_events_page(&self, url: &str) -> Result<events::EventsResponse, error::Error<'_>> {
+        events::get_events_page_with_client(self, url)
+    }
+
+    /// Send a message, then poll its events until it is delivered, fails, or `timeout`
+    /// elapses.
+    ///
+    /// Returns [`Error::MissingBody`](enum.Error.html#variant.MissingBody) if no body is set, like
+    /// [`send_message`](#method.send_message).
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// use mailgun_sdk::Client as MailGunClient;
+    /// use mailgun_sdk::events::DeliveryOutcome;
+    /// use mailgun_sdk::message::{Email, MessageBuilder};
+    /// use std::time::Duration;
+    ///
+    /// let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+    ///
+    /// let from = Email::new(None, "from@host.com");
+    /// let to = vec![Email::new(None, "to@host.com")];
+    ///
+    /// let mut builder = MessageBuilder::new("Subject Line", &from, &to);
+    /// builder.text(Some("Message body"));
+    ///
+    /// match client.send_and_track(builder.get_message(), Duration::from_secs(60)).unwrap() {
+    ///     DeliveryOutcome::Delivered(_) => println!("Delivered"),
+    ///     DeliveryOutcome::Failed(event) => println!("Failed: {:?}", event.reason),
+    ///     DeliveryOutcome::TimedOut => println!("Still pending"),
+    /// }
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn send_and_track(&self, message: &'a message::Message, timeout: Duration) -> Result<events::DeliveryOutcome, error::Error<'_>> {
+        match self.send_message(message)? {
+            message::SendMessageResponse::Success { id, message: _ } => self.track_delivery(&id, timeout),
+            response => Err(error::Error::Unknown(format!("Unexpected response: {:?}", response))),
+        }
+    }
+
+    /// Poll the events of the message with `message_id` until it is delivered, fails, or
+    /// `timeout` elapses.
+    #[cfg(feature = "blocking")]
+    pub fn track_delivery(&self, message_id: &str, timeout: Duration) -> Result<events::DeliveryOutcome, error::Error<'_>> {
+        events::track_delivery_with_client(self, message_id, timeout)
+    }
+
+    /// Stream all events matching `query`, transparently following the pages of results.
+    ///
+    /// Pages are fetched as the stream is consumed, so a slow consumer never buffers more than
+    /// a single page. The stream ends after the last page, or after the first error.
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// use futures_util::stream::StreamExt;
+    /// use mailgun_sdk::Client as MailGunClient;
+    /// use mailgun_sdk::events::EventsQuery;
+    ///
+    /// # async fn run() {
+    /// let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+    ///
+    /// let mut events = Box::pin(client.events_stream(EventsQuery::default()));
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     println!("{:?}", event.unwrap());
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn events_stream(&self, query: events::EventsQuery) -> impl futures_util::stream::Stream<Item = Result<events::Event, error::Error<'static>>> {
+        events::events_stream_with_client(self, query)
+    }
+}
+
+// Methods for the Subaccounts API.
+#[cfg(feature = "blocking")]
+impl<'a> Client<'a> {
+    /// List the subaccounts managed by this account.
+    ///
+    /// Refer to the [`subaccount`](subaccount) module documentation.
+    pub fn list_subaccounts(&self) -> Result<Vec<subaccount::Subaccount>, error::Error<'_>> {
+        subaccount::list_subaccounts_with_client(self)
+    }
+
+    /// Get a single subaccount by its `id`.
+    pub fn get_subaccount(&self, id: &str) -> Result<subaccount::Subaccount, error::Error<'_>> {
+        subaccount::get_subaccount_with_client(self, id)
+    }
+}
+
+// Methods for the Credentials API.
+#[cfg(feature = "blocking")]
+impl<'a> Client<'a> {
+    /// List the SMTP credentials of the domain.
+    ///
+    /// Refer to the [`credential`](credential) module documentation.
+    pub fn list_credentials(&self) -> Result<Vec<credential::Credential>, error::Error<'_>> {
+        credential::list_credentials_with_client(self)
+    }
+
+    /// Create new SMTP credentials for the domain.
+    pub fn create_credential(&self, login: &str, password: &str) -> Result<credential::CredentialResponse, error::Error<'_>> {
+        credential::create_credential_with_client(self, login, password)
+    }
+
+    /// Change the password of existing SMTP credentials.
+    pub fn update_credential(&self, login: &str, password: &str) -> Result<credential::CredentialResponse, error::Error<'_>> {
+        credential::update_credential_with_client(self, login, password)
+    }
+
+    /// Delete existing SMTP credentials.
+    pub fn delete_credential(&self, login: &str) -> Result<credential::CredentialResponse, error::Error<'_>> {
+        credential::delete_credential_with_client(self, login)
+    }
+}
+
+// Methods for the Domains API.
+#[cfg(feature = "blocking")]
+impl<'a> Client<'a> {
+    /// Get the domain of the client, with the sending and receiving DNS records that must be
+    /// published for it.
+    ///
+    /// Refer to the [`domain`](domain) module documentation.
+    pub fn get_domain(&self) -> Result<domain::Domain, error::Error<'_>> {
+        domain::get_domain_with_client(self)
+    }
+
+    /// Get the open, click, and unsubscribe tracking settings of the domain. The tracking
+    /// domain is returned by [`get_domain`](#method.get_domain) instead.
+    ///
+    /// Refer to the [`domain`](domain) module documentation.
+    pub fn get_tracking_settings(&self) -> Result<domain::TrackingSettings, error::Error<'_>> {
+        domain::get_tracking_settings_with_client(self)
+    }
+
+    /// Enable or disable open tracking for the domain.
+    pub fn set_open_tracking(&self, active: bool) -> Result<domain::DomainResponse, error::Error<'_>> {
+        domain::set_open_tracking_with_client(self, active)
+    }
+
+    /// Enable or disable click tracking for the domain.
+    pub fn set_click_tracking(&self, active: bool) -> Result<domain::DomainResponse, error::Error<'_>> {
+        domain::set_click_tracking_with_client(self, active)
+    }
+
+    /// Enable or disable unsubscribe tracking for the domain, optionally replacing the HTML and
+    /// text footers appended to messages.
+    pub fn set_unsubscribe_tracking(&self, active: bool, html_footer: Option<&str>, text_footer: Option<&str>) -> Result<domain::DomainResponse, error::Error<'_>> {
+        domain::set_unsubscribe_tracking_with_client(self, active, html_footer, text_footer)
+    }
+
+    /// Set the prefix of the tracking domain that tracked links and the open tracking pixel
+    /// point at, such as `track` for `track.YOUR_DOMAIN.com`.
+    ///
+    /// Refer to the [`domain`](domain) module documentation.
+    pub fn set_tracking_domain_prefix(&self, web_prefix: &str) -> Result<domain::DomainResponse, error::Error<'_>> {
+        domain::set_tracking_domain_prefix_with_client(self, web_prefix)
+    }
+
+    /// Get the TLS connection settings of the domain.
+    pub fn get_connection_settings(&self) -> Result<domain::ConnectionSettings, error::Error<'_>> {
+        domain::get_connection_settings_with_client(self)
+    }
+
+    /// Set whether messages from the domain require TLS and skip certificate verification by
+    /// default. Individual messages can still override these settings.
+    pub fn set_connection_settings(&self, require_tls: bool, skip_verification: bool) -> Result<domain::DomainResponse, error::Error<'_>> {
+        domain::set_connection_settings_with_client(self, require_tls, skip_verification)
+    }
+
+    /// Get the state of the regular and scheduled sending queues of the domain.
+    pub fn get_sending_queues(&self) -> Result<domain::QueueStatus, error::Error<'_>> {
+        domain::get_sending_queues_with_client(self)
+    }
+
+    /// Replace the DKIM key of the domain with a new key of `size` bits (`1024` or `2048`).
+    ///
+    /// The response contains the DNS record that must be published for the new key.
+    pub fn update_dkim_key_size(&self, size: u16) -> Result<domain::DkimResponse, error::Error<'_>> {
+        domain::update_dkim_key_size_with_client(self, size)
+    }
+
+    /// Immediately rotate the DKIM key of the domain.
+    pub fn rotate_dkim_key(&self) -> Result<domain::DkimResponse, error::Error<'_>> {
+        domain::rotate_dkim_key_with_client(self)
+    }
+}
+
+// Methods for the Webhooks API.
+#[cfg(feature = "blocking")]
+impl<'a> Client<'a> {
+    /// Send a test event to the webhook of `kind`, and return how the webhook URL responded.
+    ///
+    /// Refer to the [`webhook`](webhook) module documentation.
+    pub fn test_webhook(&self, kind: webhook::WebhookKind) -> Result<webhook::WebhookTestResponse, error::Error<'_>> {
+        webhook::test_webhook_with_client(self, kind)
+    }
+}
+
+// Methods for the Accounts API.
+#[cfg(feature = "blocking")]
+impl<'a> Client<'a> {
+    /// Get the sending limit of the account, and how much of it has been used in the current
+    /// period.
+    ///
+    /// Refer to the [`account`](account) module documentation.
+    pub fn get_account_usage(&self) -> Result<account::AccountUsage, error::Error<'_>> {
+        account::get_account_usage_with_client(self)
+    }
+}
+
+// Methods for the Suppressions API.
+#[cfg(feature = "blocking")]
+impl<'a> Client<'a> {
+    /// Check which suppression lists of the domain `address` is on: bounces, unsubscribes, or
+    /// complaints.
+    ///
+    /// Refer to the [`suppression`](suppression) module documentation.
+    pub fn is_suppressed(&self, address: &str) -> Result<suppression::SuppressionStatus, error::Error<'_>> {
+        suppression::is_suppressed_with_client(self, address)
+    }
+}
+
+// Methods for the Inbox Placement API.
+#[cfg(feature = "blocking")]
+impl<'a> Client<'a> {
+    /// Create an inbox placement test, sending `test` to the seed list of the account.
+    ///
+    /// Refer to the [`inbox_placement`](inbox_placement) module documentation.
+    pub fn create_inbox_placement_test(&self, test: &inbox_placement::InboxPlacementTest) -> Result<inbox_placement::CreateInboxPlacementTestResponse, error::Error<'_>> {
+        inbox_placement::create_inbox_placement_test_with_client(self, test)
+    }
+
+    /// Get where the messages of the inbox placement test with `id` landed.
+    pub fn get_inbox_placement_result(&self, id: &str) -> Result<inbox_placement::InboxPlacementResult, error::Error<'_>> {
+        inbox_placement::get_inbox_placement_result_with_client(self, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_new() {
+        let client = Client::new("api_key", "domain");
+
+        assert_eq!("api_key", client.api_key());
+        assert_eq!("domain", client.domain);
+    }
+
+    #[test]
+    fn client_send_sync_clone() {
+        fn assert_send_sync_clone<T: Send + Sync + Clone>(_: &T) {}
+
+        let client = Client::new(String::from("api_key"), String::from("domain"));
+        assert_send_sync_clone(&client);
+
+        let client: Client<'static> = client;
+        let clone = client.clone();
+
+        assert_eq!(client.api_key(), clone.api_key());
+        assert_eq!(client.domain(), clone.domain());
+    }
+
+    #[test]
+    fn client_builder() {
+        let client = ClientBuilder::new("api_key", "domain")
+            .region(Region::Eu)
+            .connect_timeout(Duration::from_secs(2))
+            .timeout(Duration::from_secs(5))
+            .user_agent("test-agent/1.0")
+            .rate_limit(50)
+            .build()
+            .unwrap();
+
+        assert_eq!("api_key", client.api_key());
+        assert_eq!("domain", client.domain());
+        assert_eq!(Region::Eu, client.region());
+        assert_eq!("https://api.eu.mailgun.net", client.base_url());
+        assert_eq!(Some(50), client.rate_limiter().map(RateLimiter::per_second));
+
+        let client = Client::new("api_key", "domain");
+
+        assert_eq!(Region::Us, client.region());
+        assert_eq!("https://api.mailgun.net", client.base_url());
+        assert!(client.rate_limiter().is_none());
+
+        let client = ClientBuilder::new("api_key", "domain").rate_limit(0).build().unwrap();
+
+        assert!(client.rate_limiter().is_none());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn client_rate_limit_paces_requests() {
+        let transport = MockTransport::new();
+
+        let client = ClientBuilder::new("api_key", "domain")
+            .rate_limit(20)
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let started = std::time::Instant::now();
+
+        for _ in 0..25 {
+            client.get_json::<serde_json::Value>("/v3/domains").unwrap();
+        }
+
+        assert_eq!(25, transport.requests().len());
+        assert!(started.elapsed() >= Duration::from_millis(200), "{:?}", started.elapsed());
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn client_rate_limit_paces_async_requests() {
+        let transport = MockTransport::new();
+
+        let client = ClientBuilder::new("api_key", "domain")
+            .rate_limit(20)
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let from = message::Email::new(None, "test@test.com");
+        let to = vec![message::Email::new(None, "test1@test.com")];
+
+        let mut message_builder = message::MessageBuilder::new("Subject Line", &from, &to);
+        message_builder.text(Some("Message body"));
+        let message = message_builder.get_message();
+
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+        let started = std::time::Instant::now();
+
+        runtime.block_on(async {
+            for _ in 0..25 {
+                client.send_message_async(message).await.unwrap();
+            }
+        });
+
+        assert_eq!(25, transport.requests().len());
+        assert!(started.elapsed() >= Duration::from_millis(200), "{:?}", started.elapsed());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn client_apply_auth() {
+        let authorization = |request: reqwest::blocking::RequestBuilder| {
+            let request = request.build().unwrap();
+            assert_eq!("https://api.mailgun.net/v3/domains", request.url().as_str());
+            request.headers()["authorization"].to_str().unwrap().to_string()
+        };
+
+        let client = Client::new("api_key", "domain");
+        assert_eq!(Some("api"), client.username());
+
+        let request = client.request(reqwest::Method::GET, "https://api.mailgun.net/v3/domains");
+        assert_eq!("Basic YXBpOmFwaV9rZXk=", authorization(request));
+
+        let request = client.apply_auth_as(client.client().get("https://api.mailgun.net/v3/domains"), "user");
+        assert_eq!("Basic dXNlcjphcGlfa2V5", authorization(request));
+
+        let client = ClientBuilder::new("", "domain")
+            .auth(Auth::Basic { username: "user".into(), api_key: "api_key".into() })
+            .build()
+            .unwrap();
+        assert_eq!(Some("user"), client.username());
+        let request = client.apply_auth(client.client().get("https://api.mailgun.net/v3/domains"));
+        assert_eq!("Basic dXNlcjphcGlfa2V5", authorization(request));
+
+        let client = ClientBuilder::new("", "domain")
+            .auth(Auth::Bearer { token: "token".into() })
+            .build()
+            .unwrap();
+        let request = client.request(reqwest::Method::GET, "https://api.mailgun.net/v3/domains");
+        assert_eq!("Bearer token", authorization(request));
+        assert_eq!("token", client.api_key());
+        assert_eq!(None, client.username());
+    }
+
+    #[test]
+    fn check_redirect_status() {
+        let mut headers = reqwest::header::HeaderMap::new();
+
+        assert!(check_redirect(reqwest::StatusCode::OK, &headers).is_ok());
+        assert!(check_redirect(reqwest::StatusCode::NOT_FOUND, &headers).is_ok());
+
+        headers.insert(reqwest::header::LOCATION, "https://example.com/".parse().unwrap());
+
+        match check_redirect(reqwest::StatusCode::FOUND, &headers) {
+            Err(error::Error::UnexpectedRedirect { status, location }) => {
+                assert_eq!(302, status);
+                assert_eq!(Some(String::from("https://example.com/")), location);
+            },
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn send_request_redirect() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v3/domains", listener.local_addr().unwrap());
+
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0; 1024]).unwrap();
+
+            stream.write_all(b"HTTP/1.1 301 Moved Permanently\r\nLocation: https://example.com/\r\nContent-Length: 0\r\n\r\n").unwrap();
+        });
+
+        let client = Client::new("api_key", "domain");
+
+        match client.send_request::<serde_json::Value>(client.request(reqwest::Method::GET, &url)) {
+            Err(error::Error::UnexpectedRedirect { status, location }) => {
+                assert_eq!(301, status);
+                assert_eq!(Some(String::from("https://example.com/")), location);
+            },
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn client_builder_from_vars() {
+        use std::collections::HashMap;
+
+        let mut vars = HashMap::new();
+        let lookup = |vars: &HashMap<&str, &str>| {
+            let vars = vars.clone();
+            ClientBuilder::from_vars(move |name| vars.get(name).map(|value| value.to_string()))
+        };
+
+        match lookup(&vars) {
+            Err(error::Error::MissingEnv(name)) => assert_eq!("MAILGUN_API_KEY", name),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        vars.insert("MAILGUN_API_KEY", "api_key");
+
+        match lookup(&vars) {
+            Err(error::Error::MissingEnv(name)) => assert_eq!("MAILGUN_DOMAIN", name),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        vars.insert("MAILGUN_DOMAIN", "domain");
+
+        let client = lookup(&vars).unwrap().build().unwrap();
+        assert_eq!("api_key", client.api_key());
+        assert_eq!("domain", client.domain());
+        assert_eq!(Region::Us, client.region());
+
+        vars.insert("MAILGUN_REGION", "EU");
+        assert_eq!(Region::Eu, lookup(&vars).unwrap().build().unwrap().region());
+
+        vars.insert("MAILGUN_REGION", "mars");
+
+        match lookup(&vars) {
+            Err(error::Error::InvalidEnv { name, value }) => {
+                assert_eq!("MAILGUN_REGION", name);
+                assert_eq!("mars", value);
+            },
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_response_status() {
+        match parse_response::<ApiErrorResponse>(reqwest::StatusCode::UNAUTHORIZED, String::from("Unauthorized")) {
+            Err(error::Error::Unauthorized) => {},
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        match parse_response::<ApiErrorResponse>(reqwest::StatusCode::FORBIDDEN, String::from("Forbidden")) {
+            Err(error::Error::ApiForbiddenError) => {},
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        match parse_response::<ApiErrorResponse>(reqwest::StatusCode::NOT_FOUND, String::from(r#"{"message": "Domain not found"}"#)) {
+            Err(error::Error::ApiError { status, message }) => {
+                assert_eq!(404, status);
+                assert_eq!("Domain not found", message);
+            },
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        let html = format!("<html><body>{}</body></html>", "x".repeat(300));
+
+        match parse_response::<ApiErrorResponse>(reqwest::StatusCode::NOT_FOUND, html.clone()) {
+            Err(error::Error::ApiError { status, message }) => {
+                assert_eq!(404, status);
+                assert_eq!(format!("{}...", &html[..BODY_PREVIEW_LENGTH]), message);
+            },
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn check_content_type_non_json() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        let html = format!("<html><body>{}</body></html>", "x".repeat(300));
+
+        assert!(check_content_type(reqwest::StatusCode::OK, &headers, &html).is_ok());
+
+        headers.insert(reqwest::header::CONTENT_TYPE, "application/json".parse().unwrap());
+        assert!(check_content_type(reqwest::StatusCode::OK, &headers, r#"{"items": []}"#).is_ok());
+
+        headers.insert(reqwest::header::CONTENT_TYPE, "text/plain".parse().unwrap());
+        assert!(check_content_type(reqwest::StatusCode::OK, &headers, r#"{"items": []}"#).is_ok());
+        assert!(check_content_type(reqwest::StatusCode::UNAUTHORIZED, &headers, "Forbidden").is_ok());
+
+        headers.insert(reqwest::header::CONTENT_TYPE, "text/html; charset=utf-8".parse().unwrap());
+        assert!(check_content_type(reqwest::StatusCode::BAD_GATEWAY, &headers, &html).is_ok());
+        assert!(check_content_type(reqwest::StatusCode::NOT_FOUND, &headers, &html).is_ok());
+
+        match check_content_type(reqwest::StatusCode::OK, &headers, &html) {
+            Err(error::Error::UnexpectedResponse { status, content_type, body_preview }) => {
+                assert_eq!(200, status);
+                assert_eq!("text/html; charset=utf-8", content_type);
+                assert_eq!(format!("{}...", &html[..BODY_PREVIEW_LENGTH]), body_preview);
+            },
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn send_request_connect_error() {
+        let client = Client::new("api_key", "domain");
+
+        match client.get_events_page("http://127.0.0.1:1/v3/domain/events") {
+            Err(error::Error::Connect(_)) => {},
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_response_decode_error() {
+        match parse_response::<ApiErrorResponse>(reqwest::StatusCode::OK, String::from("<html>")) {
+            Err(error::Error::Decode(_)) => {},
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn client_url_for() {
+        let client = Client::new("api_key", "domain");
+
+        assert_eq!("https://api.mailgun.net/v3/domains", client.url_for("/v3/domains"));
+        assert_eq!("https://api.mailgun.net/v3/domains", client.url_for("v3/domains"));
+    }
+
+    /// Call endpoints of each API through a mock client in `region`, and check that every
+    /// request went to the host of the region.
+    #[cfg(feature = "blocking")]
+    fn assert_requests_use_region(region: Region, host: &str) {
+        let transport = crate::mock::MockTransport::new();
+
+        let client = ClientBuilder::new("api_key", "test.com")
+            .region(region)
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let from = message::Email::from("from@test.com");
+        let to = message::emails(&["to@test.com"]);
+
+        let mut builder = message::MessageBuilder::new("Subject Line", &from, &to);
+        builder.text(Some("Message Body"));
+
+        let _ = client.send_message(builder.get_message());
+        let _ = client.get_events(&events::EventsQuery::default());
+        let _ = client.is_suppressed("to@test.com");
+        let _ = client.get_domain();
+        let _ = client.list_credentials();
+        let _ = client.list_subaccounts();
+        let _ = client.get_account_usage();
+        let _ = client.delete_scheduled_message("storage_key");
+
+        let requests = transport.requests();
+
+        assert_eq!(10, requests.len());
+
+        for request in &requests {
+            assert!(request.url().starts_with(&format!("https://{}/", host)), "{} sent to the wrong host", request.url());
+        }
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn client_us_region_host() {
+        assert_requests_use_region(Region::Us, "api.mailgun.net");
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn client_eu_region_host() {
+        assert_requests_use_region(Region::Eu, "api.eu.mailgun.net");
+    }
+
+    #[test]
+    fn client_debug_redacts_api_key() {
+        let builder = ClientBuilder::new("key-3ax6xnjp29jd6fds4gc373sgvjxteol0", "test.com");
+
+        assert!(!format!("{:?}", builder).contains("key-3ax6xnjp29jd6fds4gc373sgvjxteol0"));
+
+        let client = builder.build().unwrap();
+        let debug = format!("{:?}", client);
+
+        assert!(!debug.contains("key-3ax6xnjp29jd6fds4gc373sgvjxteol0"));
+        assert!(debug.contains(REDACTED));
+    }
+
+    #[test]
+    fn auth_debug_redacts_secret() {
+        let basic = Auth::basic("key-3ax6xnjp29jd6fds4gc373sgvjxteol0");
+        let bearer = Auth::Bearer { token: "token-secret".into() };
+
+        assert_eq!("Basic { username: \"api\", api_key: [REDACTED] }", format!("{:?}", basic));
+        assert_eq!("Bearer { token: [REDACTED] }", format!("{:?}", bearer));
+    }
+
+    #[test]
+    fn request_info_redacts_api_key() {
+        let info = RequestInfo::new(&reqwest::Method::POST, "https://api.mailgun.net/v3/domain/messages?key=secret", Some("text=secret"), "secret");
+
+        assert_eq!("POST", info.method);
+        assert_eq!("https://api.mailgun.net/v3/domain/messages?key=[REDACTED]", info.url);
+        assert_eq!(Some(String::from("text=[REDACTED]")), info.body);
+    }
+
+    #[test]
+    fn client_request_logger() {
+        use std::sync::{Arc, Mutex};
+
+        let logged = Arc::new(Mutex::new(Vec::new()));
+        let logged_clone = Arc::clone(&logged);
+
+        let mut client = Client::new("api_key", "domain");
+        client.set_request_logger(Box::new(move |info| {
+            logged_clone.lock().unwrap().push(info.clone());
+        }));
+
+        client.log_request(&reqwest::Method::GET, "https://api.mailgun.net/v3/domains", None);
+
+        let logged = logged.lock().unwrap();
+        assert_eq!(1, logged.len());
+        assert_eq!("GET", logged[0].method);
+    }
+
+    #[test]
+    fn client_messages_url() {
+        let client = Client::new("api_key", "domain");
+        assert_eq!("https://api.mailgun.net/v3/domain/messages", client.messages_url(client.domain()));
+
+        let client = ClientBuilder::new("api_key", "domain").region(Region::Eu).build().unwrap();
+        assert_eq!("https://api.eu.mailgun.net/v3/other/messages", client.messages_url("other"));
     }
 
     #[test]
@@ -0,0 +1,132 @@
+//! Model for SMTP credentials of a domain.
+//!
+//! Full API documentation: [https://documentation.mailgun.com/en/latest/api-credentials.html](https://documentation.mailgun.com/en/latest/api-credentials.html)
+//!
+//! Credentials are the login/password pairs used to send messages through MailGun's SMTP
+//! servers. The `login` may be given either as the local part (`alice`) or the full address
+//! (`alice@YOUR_DOMAIN.com`).
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "blocking")] {
+//! use mailgun_sdk::Client as MailGunClient;
+//!
+//! let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+//!
+//! client.create_credential("alice", "supersecret").unwrap();
+//!
+//! for credential in client.list_credentials().unwrap() {
+//!     println!("{} (created {})", credential.login, credential.created_at);
+//! }
+//! # }
+//! ```
+
+#[cfg(feature = "blocking")]
+use crate::error;
+#[cfg(feature = "blocking")]
+use crate::util::path_segment;
+use serde::Deserialize;
+
+/// SMTP credentials of a domain.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Credential {
+    /// Login of the credentials, as a full email address.
+    pub login: String,
+
+    /// Date the credentials were created, in RFC 2822 format.
+    pub created_at: String,
+}
+
+/// Response sent back from MailGun when listing credentials.
+#[cfg(feature = "blocking")]
+#[derive(Debug, Deserialize)]
+struct ListCredentialsResponse {
+    items: Vec<Credential>,
+}
+
+/// Response sent back from MailGun after creating, updating, or deleting credentials.
+#[derive(Debug, Deserialize)]
+pub struct CredentialResponse {
+    pub message: String,
+}
+
+/// List the SMTP credentials of the domain of an existing [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn list_credentials_with_client(client: &crate::Client) -> Result<Vec<Credential>, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/credentials", client.domain()));
+
+    let request = client.request(reqwest::Method::GET, &url);
+
+    client
+        .send_request::<ListCredentialsResponse>(request)
+        .map(|response| response.items)
+}
+
+/// Create new SMTP credentials with an existing [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn create_credential_with_client(client: &crate::Client, login: &str, password: &str) -> Result<CredentialResponse, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/credentials", client.domain()));
+
+    let request = client
+        .request(reqwest::Method::POST, &url)
+        .form(&[("login", login), ("password", password)]);
+
+    client.send_request(request)
+}
+
+/// Change the password of existing SMTP credentials with an existing [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn update_credential_with_client(client: &crate::Client, login: &str, password: &str) -> Result<CredentialResponse, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/credentials/{}", client.domain(), path_segment(login)));
+
+    let request = client
+        .request(reqwest::Method::PUT, &url)
+        .form(&[("password", password)]);
+
+    client.send_request(request)
+}
+
+/// Delete existing SMTP credentials with an existing [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn delete_credential_with_client(client: &crate::Client, login: &str) -> Result<CredentialResponse, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/credentials/{}", client.domain(), path_segment(login)));
+
+    let request = client.request(reqwest::Method::DELETE, &url);
+
+    client.send_request(request)
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_credentials_response_deserialize() {
+        let body = r#"{
+            "total_count": 2,
+            "items": [
+                {
+                    "size_bytes": 0,
+                    "created_at": "Tue, 27 Sep 2011 20:24:22 GMT",
+                    "mailbox": "user@samples.mailgun.org",
+                    "login": "user@samples.mailgun.org"
+                },
+                {
+                    "size_bytes": 0,
+                    "created_at": "Thu, 06 Oct 2011 10:22:36 GMT",
+                    "mailbox": "user2@samples.mailgun.org",
+                    "login": "user2@samples.mailgun.org"
+                }
+            ]
+        }"#;
+
+        let response = serde_json::from_str::<ListCredentialsResponse>(body).unwrap();
+
+        assert_eq!(2, response.items.len());
+        assert_eq!(Credential {
+            login: String::from("user@samples.mailgun.org"),
+            created_at: String::from("Tue, 27 Sep 2011 20:24:22 GMT"),
+        }, response.items[0]);
+    }
+}
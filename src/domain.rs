@@ -0,0 +1,603 @@
+//! Model for the settings of a sending domain.
+//!
+//! Full API documentation: [https://documentation.mailgun.com/en/latest/api-domains.html](https://documentation.mailgun.com/en/latest/api-domains.html)
+//!
+//! These settings apply to every message sent from the domain of the
+//! [`Client`](../struct.Client.html). Most of them can be overridden per message with the
+//! matching `option_*` fields of [`MessageBuilder`](../message/struct.MessageBuilder.html).
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "blocking")] {
+//! use mailgun_sdk::Client as MailGunClient;
+//!
+//! let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+//!
+//! let settings = client.get_tracking_settings().unwrap();
+//!
+//! if !settings.open.active {
+//!     client.set_open_tracking(true).unwrap();
+//! }
+//! # }
+//! ```
+//!
+//! ### Per-Message Tracking
+//!
+//! The tracking options of a message take precedence over the tracking settings of the domain,
+//! both to enable tracking the domain has turned off and to disable tracking it has turned on:
+//!
+//! 1. [`MessageBuilder::tracking_opens`](../message/struct.MessageBuilder.html#method.tracking_opens)
+//!    (`o:tracking-opens`) and [`MessageBuilder::tracking_clicks`](../message/struct.MessageBuilder.html#method.tracking_clicks)
+//!    (`o:tracking-clicks`) decide open and click tracking for the message.
+//! 2. [`MessageBuilder::tracking`](../message/struct.MessageBuilder.html#method.tracking)
+//!    (`o:tracking`) toggles both at once. MailGun does not document which option wins when it
+//!    disagrees with the two above, so set one or the other.
+//! 3. Options left unset fall back to the settings of the domain.
+//!
+//! Unsubscribe tracking has no per-message option, and always follows the domain.
+//!
+//! ### Tracking Domain
+//!
+//! Tracked links and the open tracking pixel point at the tracking domain of the sending
+//! domain, `email.YOUR_DOMAIN.com` by default, read with
+//! [`Domain::tracking_domain`](struct.Domain.html#method.tracking_domain) from
+//! [`Client::get_domain`](../struct.Client.html#method.get_domain). Branded tracking links use
+//! a custom prefix, set with [`Client::set_tracking_domain_prefix`](../struct.Client.html#method.set_tracking_domain_prefix)
+//! once a CNAME record for it points at MailGun. The tracking domain is a setting of the
+//! domain: [`MessageBuilder::tracking`](../message/struct.MessageBuilder.html#method.tracking)
+//! (`o:tracking`) only decides whether the links of a message are rewritten, not where they
+//! point.
+//!
+//! ### Domain Verification Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "blocking")] {
+//! use mailgun_sdk::Client as MailGunClient;
+//!
+//! let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+//!
+//! let domain = client.get_domain().unwrap();
+//!
+//! for record in domain.unverified_records() {
+//!     println!("Publish {} {} {}", record.record_type, record.name, record.value);
+//! }
+//! # }
+//! ```
+
+#[cfg(feature = "blocking")]
+use crate::error;
+#[cfg(feature = "blocking")]
+use crate::util::yes_no;
+use serde::{Deserialize, Deserializer};
+
+/// Tracking settings of a domain.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct TrackingSettings {
+    /// Open tracking settings.
+    pub open: TrackingToggle,
+
+    /// Click tracking settings.
+    pub click: TrackingToggle,
+
+    /// Unsubscribe tracking settings.
+    pub unsubscribe: UnsubscribeSettings,
+}
+
+/// Whether a kind of tracking is enabled for a domain.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct TrackingToggle {
+    #[serde(deserialize_with = "deserialize_active")]
+    pub active: bool,
+}
+
+/// Unsubscribe tracking settings of a domain.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct UnsubscribeSettings {
+    #[serde(deserialize_with = "deserialize_active")]
+    pub active: bool,
+
+    /// Footer appended to the HTML part of messages.
+    pub html_footer: Option<String>,
+
+    /// Footer appended to the text part of messages.
+    pub text_footer: Option<String>,
+}
+
+/// DNS record that must be published for a domain.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct DnsRecord {
+    /// Type of the record, such as `TXT`, `CNAME`, or `MX`.
+    pub record_type: String,
+
+    /// Host name of the record.
+    #[serde(default)]
+    pub name: String,
+
+    /// Value of the record.
+    pub value: String,
+
+    /// Verification state of the record, such as `valid` or `unknown`.
+    pub valid: String,
+}
+
+impl DnsRecord {
+    /// Whether MailGun has verified that the record is published.
+    pub fn is_valid(&self) -> bool {
+        self.valid == "valid"
+    }
+}
+
+/// Response sent back from MailGun after changing the DKIM key of a domain.
+#[derive(Debug, Deserialize)]
+pub struct DkimResponse {
+    pub message: String,
+
+    /// DNS records to publish for the new DKIM key.
+    #[serde(default)]
+    pub sending_dns_records: Vec<DnsRecord>,
+}
+
+/// Connection settings of a domain.
+///
+/// These are the defaults for the [`option_require_tls`](../message/struct.MessageBuilder.html#method.option_require_tls)
+/// and [`option_skip_verification`](../message/struct.MessageBuilder.html#method.option_skip_verification)
+/// fields of a message, which override them when set.
+///
+/// The default settings match those of a new domain, with both options disabled.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct ConnectionSettings {
+    /// Whether messages must be delivered over a TLS connection.
+    pub require_tls: bool,
+
+    /// Whether certificate and hostname verification is skipped when establishing a TLS
+    /// connection.
+    pub skip_verification: bool,
+}
+
+/// State of the sending queues of a domain.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct QueueStatus {
+    /// Queue of messages scheduled for later delivery with `o:deliverytime`.
+    pub scheduled: SendingQueue,
+
+    /// Queue of messages to deliver immediately.
+    pub regular: SendingQueue,
+}
+
+/// State of a single sending queue.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct SendingQueue {
+    /// Whether MailGun has stopped delivering the messages of the queue.
+    pub is_disabled: bool,
+
+    /// Why and until when the queue is disabled.
+    #[serde(default)]
+    pub disabled: Option<QueueDisabled>,
+}
+
+/// Details of a disabled sending queue.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct QueueDisabled {
+    /// Time delivery resumes, as an RFC 2822 date. Empty if the queue is not disabled.
+    #[serde(default)]
+    pub until: String,
+
+    /// Reason the queue is disabled. Empty if the queue is not disabled.
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// Response sent back from MailGun when fetching connection settings.
+#[cfg(feature = "blocking")]
+#[derive(Debug, Deserialize)]
+struct GetConnectionSettingsResponse {
+    connection: ConnectionSettings,
+}
+
+/// Response sent back from MailGun when fetching tracking settings.
+#[cfg(feature = "blocking")]
+#[derive(Debug, Deserialize)]
+struct GetTrackingSettingsResponse {
+    tracking: TrackingSettings,
+}
+
+/// Sending domain, with the DNS records that must be published for it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Domain {
+    /// Name of the domain, such as `YOUR_DOMAIN.com`.
+    pub name: String,
+
+    /// State of the domain, such as `active` or `unverified`.
+    pub state: String,
+
+    /// Prefix of the tracking domain, such as `email`.
+    pub web_prefix: Option<String>,
+
+    /// DNS records needed to send messages from the domain, such as SPF and DKIM.
+    pub sending_dns_records: Vec<DnsRecord>,
+
+    /// DNS records needed to receive messages at the domain, typically MX records.
+    pub receiving_dns_records: Vec<DnsRecord>,
+}
+
+impl Domain {
+    /// Host name of the tracking domain, made of the prefix and the name of the domain.
+    pub fn tracking_domain(&self) -> Option<String> {
+        self.web_prefix
+            .as_deref()
+            .filter(|web_prefix| !web_prefix.is_empty())
+            .map(|web_prefix| format!("{}.{}", web_prefix, self.name))
+    }
+
+    /// Return the sending and receiving DNS records MailGun has not verified yet, in that order.
+    pub fn unverified_records(&self) -> Vec<&DnsRecord> {
+        self.sending_dns_records
+            .iter()
+            .chain(self.receiving_dns_records.iter())
+            .filter(|record| !record.is_valid())
+            .collect()
+    }
+}
+
+/// Response sent back from MailGun when fetching a domain.
+#[derive(Debug, Deserialize)]
+struct GetDomainResponse {
+    domain: DomainInfo,
+
+    #[serde(default)]
+    sending_dns_records: Vec<DnsRecord>,
+
+    #[serde(default)]
+    receiving_dns_records: Vec<DnsRecord>,
+}
+
+/// Fields of the `domain` object of a [`GetDomainResponse`].
+#[derive(Debug, Deserialize)]
+struct DomainInfo {
+    name: String,
+
+    #[serde(default)]
+    state: String,
+
+    #[serde(default)]
+    web_prefix: Option<String>,
+}
+
+impl From<GetDomainResponse> for Domain {
+    fn from(response: GetDomainResponse) -> Domain {
+        Domain {
+            name: response.domain.name,
+            state: response.domain.state,
+            web_prefix: response.domain.web_prefix,
+            sending_dns_records: response.sending_dns_records,
+            receiving_dns_records: response.receiving_dns_records,
+        }
+    }
+}
+
+/// Response sent back from MailGun after updating the settings of a domain.
+#[derive(Debug, Deserialize)]
+pub struct DomainResponse {
+    pub message: String,
+}
+
+/// MailGun reports the `active` flag either as a boolean or as a string (`"yes"`, `"no"`,
+/// `"true"`, `"false"`, or `"htmlonly"` for click tracking).
+fn deserialize_active<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Active {
+        Bool(bool),
+        Text(String),
+    }
+
+    match Active::deserialize(deserializer)? {
+        Active::Bool(active) => Ok(active),
+        Active::Text(active) => Ok(active != "no" && active != "false"),
+    }
+}
+
+/// Get the tracking settings of the domain of an existing [`Client`](../struct.Client.html).
+///
+/// The tracking domain is not part of these settings: read it with
+/// [`Domain::tracking_domain`](struct.Domain.html#method.tracking_domain).
+#[cfg(feature = "blocking")]
+pub fn get_tracking_settings_with_client(client: &crate::Client) -> Result<TrackingSettings, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/tracking", client.domain()));
+
+    let request = client.request(reqwest::Method::GET, &url);
+
+    client
+        .send_request::<GetTrackingSettingsResponse>(request)
+        .map(|response| response.tracking)
+}
+
+/// Get the domain of an existing [`Client`](../struct.Client.html), with the DNS records that
+/// must be published for it.
+#[cfg(feature = "blocking")]
+pub fn get_domain_with_client(client: &crate::Client) -> Result<Domain, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}", client.domain()));
+
+    let request = client.request(reqwest::Method::GET, &url);
+
+    client.send_request::<GetDomainResponse>(request).map(Domain::from)
+}
+
+/// Set the prefix of the tracking domain, such as `track` for `track.YOUR_DOMAIN.com`, with an
+/// existing [`Client`](../struct.Client.html). MailGun calls it the `web_prefix` of the domain.
+///
+/// A CNAME record for the new tracking domain must point at MailGun before tracked links work.
+#[cfg(feature = "blocking")]
+pub fn set_tracking_domain_prefix_with_client(client: &crate::Client, web_prefix: &str) -> Result<DomainResponse, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/web_prefix", client.domain()));
+
+    let request = client
+        .request(reqwest::Method::PUT, &url)
+        .form(&[("web_prefix", web_prefix)]);
+
+    client.send_request(request)
+}
+
+/// Enable or disable open tracking with an existing [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn set_open_tracking_with_client(client: &crate::Client, active: bool) -> Result<DomainResponse, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/tracking/open", client.domain()));
+
+    let request = client
+        .request(reqwest::Method::PUT, &url)
+        .form(&[("active", yes_no(active))]);
+
+    client.send_request(request)
+}
+
+/// Enable or disable click tracking with an existing [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn set_click_tracking_with_client(client: &crate::Client, active: bool) -> Result<DomainResponse, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/tracking/click", client.domain()));
+
+    let request = client
+        .request(reqwest::Method::PUT, &url)
+        .form(&[("active", yes_no(active))]);
+
+    client.send_request(request)
+}
+
+/// Enable or disable unsubscribe tracking with an existing [`Client`](../struct.Client.html).
+///
+/// The footers are left unchanged when set to `None`.
+#[cfg(feature = "blocking")]
+pub fn set_unsubscribe_tracking_with_client(
+    client: &crate::Client,
+    active: bool,
+    html_footer: Option<&str>,
+    text_footer: Option<&str>,
+) -> Result<DomainResponse, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/tracking/unsubscribe", client.domain()));
+
+    let mut params = vec![("active", yes_no(active))];
+
+    if let Some(html_footer) = html_footer {
+        params.push(("html_footer", html_footer));
+    }
+
+    if let Some(text_footer) = text_footer {
+        params.push(("text_footer", text_footer));
+    }
+
+    let request = client
+        .request(reqwest::Method::PUT, &url)
+        .form(&params);
+
+    client.send_request(request)
+}
+
+/// Get the connection settings of the domain of an existing [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn get_connection_settings_with_client(client: &crate::Client) -> Result<ConnectionSettings, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/connection", client.domain()));
+
+    let request = client.request(reqwest::Method::GET, &url);
+
+    client
+        .send_request::<GetConnectionSettingsResponse>(request)
+        .map(|response| response.connection)
+}
+
+/// Update the connection settings of the domain with an existing
+/// [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn set_connection_settings_with_client(client: &crate::Client, require_tls: bool, skip_verification: bool) -> Result<DomainResponse, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/connection", client.domain()));
+
+    let request = client
+        .request(reqwest::Method::PUT, &url)
+        .form(&[
+            ("require_tls", require_tls.to_string()),
+            ("skip_verification", skip_verification.to_string()),
+        ]);
+
+    client.send_request(request)
+}
+
+/// Get the state of the sending queues of the domain of an existing
+/// [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn get_sending_queues_with_client(client: &crate::Client) -> Result<QueueStatus, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/sending_queues", client.domain()));
+
+    let request = client.request(reqwest::Method::GET, &url);
+
+    client.send_request(request)
+}
+
+/// Change the size of the DKIM key of the domain with an existing
+/// [`Client`](../struct.Client.html).
+///
+/// MailGun generates a new key of `size` bits (`1024` or `2048`) and returns the DNS record
+/// that must be published for it.
+#[cfg(feature = "blocking")]
+pub fn update_dkim_key_size_with_client(client: &crate::Client, size: u16) -> Result<DkimResponse, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/dkim_authority", client.domain()));
+
+    let size = size.to_string();
+
+    let request = client
+        .request(reqwest::Method::PUT, &url)
+        .form(&[("self", "true"), ("dkim_key_size", size.as_str())]);
+
+    client.send_request(request)
+}
+
+/// Immediately rotate the DKIM key of the domain with an existing
+/// [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn rotate_dkim_key_with_client(client: &crate::Client) -> Result<DkimResponse, error::Error<'static>> {
+    let url = client.url_for(&format!("/v1/dkim_management/domains/{}/rotate", client.domain()));
+
+    let request = client.request(reqwest::Method::POST, &url);
+
+    client.send_request(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn get_tracking_settings_response_deserialize() {
+        let body = r#"{
+            "tracking": {
+                "click": { "active": "htmlonly" },
+                "open": { "active": false },
+                "unsubscribe": {
+                    "active": "yes",
+                    "html_footer": "\n<br>\n<p><a href=\"%unsubscribe_url%\">unsubscribe</a></p>\n",
+                    "text_footer": "\n\nTo unsubscribe click: <%unsubscribe_url%>\n\n"
+                }
+            }
+        }"#;
+
+        let response = serde_json::from_str::<GetTrackingSettingsResponse>(body).unwrap();
+
+        assert!(response.tracking.click.active);
+        assert!(!response.tracking.open.active);
+        assert!(response.tracking.unsubscribe.active);
+        assert!(response.tracking.unsubscribe.text_footer.unwrap().contains("%unsubscribe_url%"));
+    }
+
+    #[test]
+    fn get_domain_response_tracking_domain() {
+        let body = r#"{
+            "domain": {
+                "name": "example.com",
+                "state": "active",
+                "web_prefix": "track",
+                "web_scheme": "https"
+            },
+            "receiving_dns_records": [],
+            "sending_dns_records": []
+        }"#;
+
+        let domain = Domain::from(serde_json::from_str::<GetDomainResponse>(body).unwrap());
+
+        assert_eq!(Some(String::from("track.example.com")), domain.tracking_domain());
+
+        let domain = Domain::from(serde_json::from_str::<GetDomainResponse>(r#"{ "domain": { "name": "example.com" } }"#).unwrap());
+
+        assert_eq!(None, domain.tracking_domain());
+        assert!(domain.unverified_records().is_empty());
+    }
+
+    #[test]
+    fn get_domain_response_unverified_records() {
+        let body = r#"{
+            "domain": { "name": "example.com", "state": "unverified" },
+            "receiving_dns_records": [
+                { "priority": "10", "record_type": "MX", "valid": "unknown", "value": "mxa.mailgun.org" },
+                { "priority": "10", "record_type": "MX", "valid": "valid", "value": "mxb.mailgun.org" }
+            ],
+            "sending_dns_records": [
+                { "record_type": "TXT", "valid": "valid", "name": "example.com", "value": "v=spf1 include:mailgun.org ~all" },
+                { "record_type": "TXT", "valid": "unknown", "name": "k1._domainkey.example.com", "value": "k=rsa; p=MIGfMA0GCSqGSIb3DQEBAQUAA4GNADCBiQKBgQC" }
+            ]
+        }"#;
+
+        let domain = Domain::from(serde_json::from_str::<GetDomainResponse>(body).unwrap());
+
+        assert_eq!("unverified", domain.state);
+        assert_eq!(2, domain.sending_dns_records.len());
+        assert_eq!(2, domain.receiving_dns_records.len());
+
+        let unverified = domain.unverified_records();
+
+        assert_eq!(2, unverified.len());
+        assert_eq!("k1._domainkey.example.com", unverified[0].name);
+        assert_eq!("mxa.mailgun.org", unverified[1].value);
+    }
+
+    #[test]
+    fn dkim_response_deserialize() {
+        let body = r#"{
+            "changed": true,
+            "message": "Domain DKIM authority has been changed",
+            "sending_dns_records": [
+                {
+                    "record_type": "TXT",
+                    "valid": "unknown",
+                    "name": "pdk1._domainkey.example.com",
+                    "value": "k=rsa; p=MIGfMA0GCSqGSIb3DQEBAQUAA4GNADCBiQKBgQC"
+                }
+            ]
+        }"#;
+
+        let response = serde_json::from_str::<DkimResponse>(body).unwrap();
+
+        assert_eq!(1, response.sending_dns_records.len());
+        assert_eq!("pdk1._domainkey.example.com", response.sending_dns_records[0].name);
+        assert!(!response.sending_dns_records[0].is_valid());
+
+        let response = serde_json::from_str::<DkimResponse>(r#"{ "message": "Rotation started" }"#).unwrap();
+
+        assert!(response.sending_dns_records.is_empty());
+    }
+
+    #[test]
+    fn queue_status_deserialize() {
+        let body = r#"{
+            "scheduled": {
+                "is_disabled": false,
+                "disabled": { "until": "", "reason": "" }
+            },
+            "regular": {
+                "is_disabled": true,
+                "disabled": { "until": "Mon, 16 Oct 2026 10:00:00 UTC", "reason": "Sending limit exceeded" }
+            }
+        }"#;
+
+        let status = serde_json::from_str::<QueueStatus>(body).unwrap();
+
+        assert!(!status.scheduled.is_disabled);
+        assert!(status.regular.is_disabled);
+        assert_eq!("Sending limit exceeded", status.regular.disabled.unwrap().reason);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn get_connection_settings_response_deserialize() {
+        let body = r#"{
+            "connection": {
+                "require_tls": true,
+                "skip_verification": false
+            }
+        }"#;
+
+        let response = serde_json::from_str::<GetConnectionSettingsResponse>(body).unwrap();
+
+        assert_eq!(ConnectionSettings { require_tls: true, skip_verification: false }, response.connection);
+    }
+}
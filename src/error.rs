@@ -4,23 +4,156 @@ use std::fmt;
 use std::io;
 
 /// Wrapper around the various errors the library might experience.
+///
+/// New variants may be added in minor releases, so a `match` on an error must include a
+/// wildcard arm:
+///
+/// ```no_run
+/// # #[cfg(feature = "blocking")] {
+/// use mailgun_sdk::{Client, Error};
+///
+/// let client = Client::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+///
+/// match client.get_account_usage() {
+///     Ok(usage) => println!("{} messages left", usage.remaining()),
+///     Err(Error::Unauthorized) => eprintln!("Check the API key"),
+///     Err(error) => eprintln!("{}", error),
+/// }
+/// # }
+/// ```
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error<'a> {
-    /// Returned when the user does not have access to part (or all) of an API. Typically, this
-    /// is thrown when an invalid API key is used.
+    /// Returned when the user does not have access to part (or all) of an API, such as when
+    /// the API key lacks the permissions for an endpoint.
     ApiForbiddenError,
 
+    /// Returned when MailGun responds to an API request with an unsuccessful status code.
+    /// `message` is the `message` field of the response body, or the start of the body when it
+    /// is not JSON, such as an HTML error page sent by a proxy.
+    ApiError {
+        status: u16,
+        message: String,
+    },
+
+    /// Returned when a message has more campaign IDs than MailGun accepts.
+    InvalidCampaign(String),
+
+    /// Returned when MailGun rejects a message because of the value of one of its fields, such
+    /// as a malformed `from` address. `field` is the name of the field as MailGun reports it.
+    InvalidField {
+        field: String,
+        message: String,
+    },
+
+    /// Returned when a connection to MailGun cannot be established, such as when DNS
+    /// resolution or the TLS handshake fails.
+    Connect(String),
+
+    /// Returned when a request to MailGun times out.
+    Timeout(String),
+
+    /// Returned when the response from MailGun cannot be read or deserialized.
+    Decode(String),
+
+    /// Returned when a delivery time cannot be parsed. Holds the rejected value.
+    InvalidDeliveryTime(String),
+
+    /// Returned when an email address cannot be parsed.
+    InvalidEmail(String),
+
+    /// Returned when the key of a custom `o:` option is invalid.
+    InvalidOption(String),
+
+    /// Returned when pre-serialized recipient variables are not a JSON object.
+    InvalidRecipientVariables(String),
+
+    /// Returned when the URL of a stored message is not an HTTPS URL on a MailGun API host, so
+    /// the credentials of the client are not sent to it. Holds the rejected URL.
+    InvalidStorageUrl(String),
+
+    /// Returned when a tag does not meet MailGun's limits on the number of tags or their length.
+    InvalidTag(String),
+
     /// Returned when serializing part of a [`Message`](message/struct.Message.html) fails.
     MessageError(serde_json::Error),
 
     /// Returned when the message body could not be formed into a `multipart/form-data` body.
     MessageBodyError(io::Error),
 
+    /// Returned when a message has no body: none of its text, HTML, or template is set.
+    MissingBody,
+
+    /// Returned when a required environment variable is not set. Holds the name of the
+    /// variable.
+    MissingEnv(String),
+
+    /// Returned when an environment variable has an unsupported value.
+    InvalidEnv {
+        name: String,
+        value: String,
+    },
+
+    /// Returned when a message is larger than the size MailGun accepts.
+    MessageTooLarge {
+        size: u64,
+        limit: u64,
+    },
+
     /// Returned when the message itself could not be formed into a `multipart/form-data` message.
     MessageParamsError(multipart::client::lazy::LazyIoError<'a>),
 
+    /// Returned when a message sent from a sandbox domain has a recipient that is not one of
+    /// the authorized recipients of the account. Sandbox domains may only send to addresses
+    /// added to the authorized recipients in the account settings, until a domain of your own
+    /// is added.
+    ///
+    /// MailGun does not say which recipient was rejected, so `recipient` lists every `to`
+    /// recipient of the message.
+    SandboxRecipientNotAuthorized {
+        recipient: String,
+    },
+
     /// Returned when MailGun responds with an error when sending a message.
-    SendMessageError(message::SendMessageResponse),
+    ///
+    /// `raw_body` holds the full response body, including any fields not captured by the
+    /// parsed `response`.
+    SendMessageError {
+        response: message::SendMessageResponse,
+        raw_body: String,
+    },
+
+    /// Returned when a stored message no longer exists, typically because MailGun only keeps
+    /// stored messages for 3 days. Holds the URL of the message.
+    StoredMessageExpired(String),
+
+    /// Returned when a batch message has more recipients than MailGun accepts.
+    TooManyRecipients {
+        count: usize,
+        limit: usize,
+    },
+
+    /// Returned when a successful response is not JSON, such as an HTML page sent by a proxy or
+    /// gateway in front of MailGun. `body_preview` holds the start of the body.
+    ///
+    /// Unsuccessful responses are returned as [`ApiError`](#variant.ApiError) whatever their
+    /// body, so their status is never lost.
+    UnexpectedResponse {
+        status: u16,
+        content_type: String,
+        body_preview: String,
+    },
+
+    /// Returned when MailGun responds with a redirect that is not followed, as allowed by the
+    /// [`RedirectPolicy`](enum.RedirectPolicy.html) of the client.
+    UnexpectedRedirect {
+        status: u16,
+        location: Option<String>,
+    },
+
+    /// Returned when MailGun rejects the credentials of a request, typically because the API
+    /// key is invalid.
+    Unauthorized,
 
     /// Returned for generic errors.
     Unknown(String),
@@ -30,13 +163,49 @@ impl<'a> fmt::Display for Error<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::ApiForbiddenError => write!(f, "API Forbidden Error"),
+            Self::ApiError { status, message } => write!(f, "API Error ({}): {}", status, message),
+            Self::Connect(error) => write!(f, "Connect Error: {}", error),
+            Self::Timeout(error) => write!(f, "Timeout Error: {}", error),
+            Self::Decode(error) => write!(f, "Decode Error: {}", error),
+            Self::InvalidCampaign(error) => write!(f, "Invalid Campaign: {}", error),
+            Self::InvalidField { field, message } => write!(f, "Invalid Field ({}): {}", field, message),
+            Self::InvalidDeliveryTime(value) => write!(f, "Invalid Delivery Time: {}", value),
+            Self::InvalidEmail(email) => write!(f, "Invalid Email: {}", email),
+            Self::InvalidOption(error) => write!(f, "Invalid Option: {}", error),
+            Self::InvalidRecipientVariables(error) => write!(f, "Invalid Recipient Variables: {}", error),
+            Self::InvalidStorageUrl(url) => write!(f, "Invalid Storage URL: {}", url),
+            Self::InvalidTag(error) => write!(f, "Invalid Tag: {}", error),
             Self::MessageError(error) => write!(f, "Message Error: {}", error),
             Self::MessageBodyError(error) => write!(f, "Message Body Error: {}", error),
+            Self::MissingBody => write!(f, "Missing Body: set the text, HTML, or template of the message"),
+            Self::MissingEnv(name) => write!(f, "Missing Environment Variable: {}", name),
+            Self::InvalidEnv { name, value } => write!(f, "Invalid Environment Variable: {}={}", name, value),
+            Self::MessageTooLarge { size, limit } => write!(f, "Message Too Large: {} bytes exceeds the limit of {} bytes", size, limit),
             Self::MessageParamsError(error) => write!(f, "Message Params Error: {}", error),
-            Self::SendMessageError(error) => write!(f, "Send Message Error: {:?}", error),
+            Self::SandboxRecipientNotAuthorized { recipient } => write!(f, "Sandbox Recipient Not Authorized: {} (add it to the authorized recipients of the account, or send from your own domain)", recipient),
+            Self::SendMessageError { response, raw_body: _ } => write!(f, "Send Message Error: {:?}", response),
+            Self::StoredMessageExpired(url) => write!(f, "Stored Message Expired: {}", url),
+            Self::TooManyRecipients { count, limit } => write!(f, "Too Many Recipients: {} exceeds the limit of {}", count, limit),
+            Self::UnexpectedResponse { status, content_type, body_preview } => write!(f, "Unexpected Response ({}, {}): {}", status, content_type, body_preview),
+            Self::UnexpectedRedirect { status, location } => write!(f, "Unexpected Redirect ({}): {}", status, location.as_deref().unwrap_or("no location")),
+            Self::Unauthorized => write!(f, "Unauthorized Error"),
             Self::Unknown(error) => write!(f, "Unknown Error: {}", error),
         }
     }
 }
 
 impl<'a> error::Error for Error<'a> {}
+
+impl<'a> From<reqwest::Error> for Error<'a> {
+    fn from(error: reqwest::Error) -> Error<'a> {
+        if error.is_timeout() {
+            Self::Timeout(error.to_string())
+        } else if error.is_connect() {
+            Self::Connect(error.to_string())
+        } else if error.is_decode() || error.is_body() {
+            Self::Decode(error.to_string())
+        } else {
+            Self::Unknown(error.to_string())
+        }
+    }
+}
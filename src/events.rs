@@ -0,0 +1,634 @@
+//! Model for events that happened to messages sent through MailGun.
+//!
+//! Full API documentation: [https://documentation.mailgun.com/en/latest/api-events.html](https://documentation.mailgun.com/en/latest/api-events.html)
+//!
+//! Events are returned a page at a time. Each [`EventsResponse`](struct.EventsResponse.html)
+//! contains the URL of the next page in its [`paging`](struct.Paging.html) field, which can be
+//! fetched with [`Client::get_events_page`](../struct.Client.html#method.get_events_page). The
+//! last page is reached once a page contains no events.
+//!
+//! [`Client::send_and_track`](../struct.Client.html#method.send_and_track) sends a message and
+//! polls its events until it is delivered or fails.
+//!
+//! [`Client::iter_events`](../struct.Client.html#method.iter_events) follows the pages
+//! automatically and yields events one at a time, fetching each page as it is needed.
+//!
+//! With the `async` feature enabled, [`Client::events_stream`](../struct.Client.html#method.events_stream)
+//! follows the pages automatically and yields events one at a time.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "blocking")] {
+//! use mailgun_sdk::Client as MailGunClient;
+//! use mailgun_sdk::events::EventsQuery;
+//!
+//! let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+//!
+//! let query = EventsQuery {
+//!     event: Some(String::from("failed")),
+//!     limit: Some(100),
+//!     ..Default::default()
+//! };
+//!
+//! let mut response = client.get_events(&query).unwrap();
+//!
+//! while !response.items.is_empty() {
+//!     for event in &response.items {
+//!         println!("{}: {:?}", event.event, event.recipient);
+//!     }
+//!
+//!     response = client.get_events_page(&response.paging.next).unwrap();
+//! }
+//! # }
+//! ```
+
+use crate::error;
+use serde::Deserialize;
+#[cfg(feature = "blocking")]
+use std::thread;
+use std::time::Duration;
+#[cfg(feature = "blocking")]
+use std::time::Instant;
+
+/// Maximum number of events MailGun returns per page.
+pub const MAX_PAGE_LIMIT: u32 = 300;
+
+/// Time to wait between two polls of the events of a message while tracking its delivery.
+pub const TRACK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// An event that happened to a message, such as it being delivered or opened.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[non_exhaustive]
+pub struct Event {
+    /// Unique identifier of the event.
+    pub id: String,
+
+    /// Type of the event, such as `accepted`, `delivered`, `failed`, or `opened`.
+    pub event: String,
+
+    /// Time the event happened, as a Unix timestamp.
+    pub timestamp: f64,
+
+    /// Severity of the event, such as `info`, `warn`, or `error`.
+    #[serde(rename = "log-level", default)]
+    pub log_level: Option<String>,
+
+    /// Recipient the event relates to.
+    #[serde(default)]
+    pub recipient: Option<String>,
+
+    /// Tags of the message.
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// For `failed` events, whether the failure is `temporary` or `permanent`.
+    #[serde(default)]
+    pub severity: Option<String>,
+
+    /// For `failed` events, the reason of the failure.
+    #[serde(default)]
+    pub reason: Option<String>,
+
+    /// Message the event relates to.
+    #[serde(default)]
+    pub message: Option<EventMessage>,
+
+    /// For `accepted` and `stored` events, where the message is stored. Stored messages
+    /// expire after 3 days.
+    #[serde(default)]
+    pub storage: Option<EventStorage>,
+
+    /// For `delivered` and `failed` events, details of the delivery attempt, such as whether it
+    /// used TLS.
+    #[serde(rename = "delivery-status", default)]
+    pub delivery_status: Option<DeliveryStatus>,
+}
+
+impl Event {
+    /// URL of the stored message the event relates to, which can be passed to
+    /// [`Client::get_stored_message`](../struct.Client.html#method.get_stored_message).
+    pub fn storage_url(&self) -> Option<&str> {
+        self.storage.as_ref().map(|storage| storage.url.as_str())
+    }
+
+    /// Key of the stored message the event relates to, which can be passed to
+    /// [`Client::delete_scheduled_message`](../struct.Client.html#method.delete_scheduled_message).
+    pub fn storage_key(&self) -> Option<&str> {
+        self.storage.as_ref().map(|storage| storage.key.as_str())
+    }
+
+    /// Whether the delivery attempt the event relates to used TLS, or `None` if the event has
+    /// no delivery status. Useful to confirm that messages sent with
+    /// [`option_require_tls`](../message/struct.MessageBuilder.html#method.option_require_tls)
+    /// were delivered over TLS.
+    pub fn used_tls(&self) -> Option<bool> {
+        self.delivery_status.as_ref().and_then(|delivery_status| delivery_status.tls)
+    }
+}
+
+/// Details of a delivery attempt, as reported by the receiving mail server.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[non_exhaustive]
+pub struct DeliveryStatus {
+    /// Whether the connection to the receiving server used TLS.
+    #[serde(default)]
+    pub tls: Option<bool>,
+
+    /// Whether the certificate of the receiving server was verified.
+    #[serde(rename = "certificate-verified", default)]
+    pub certificate_verified: Option<bool>,
+
+    /// Host name of the receiving mail server.
+    #[serde(rename = "mx-host", default)]
+    pub mx_host: Option<String>,
+
+    /// SMTP status code returned by the receiving server, such as `250`.
+    #[serde(default)]
+    pub code: Option<u16>,
+
+    /// SMTP response message returned by the receiving server.
+    #[serde(default)]
+    pub message: Option<String>,
+
+    /// Additional description of the status, mostly for failures.
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Number of the delivery attempt, starting at `1`.
+    #[serde(rename = "attempt-no", default)]
+    pub attempt_no: Option<u32>,
+}
+
+/// Location of a stored message.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct EventStorage {
+    /// URL of the stored message, which can be fetched with
+    /// [`Client::get_stored_message`](../struct.Client.html#method.get_stored_message).
+    pub url: String,
+
+    /// Key of the stored message.
+    pub key: String,
+}
+
+/// Message an event relates to.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct EventMessage {
+    /// Main headers of the message.
+    #[serde(default)]
+    pub headers: EventMessageHeaders,
+
+    /// Size of the message, in bytes.
+    #[serde(default)]
+    pub size: Option<u64>,
+}
+
+/// Main headers of the message an event relates to.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct EventMessageHeaders {
+    /// ID of the message, without the surrounding angle brackets.
+    #[serde(rename = "message-id", default)]
+    pub message_id: Option<String>,
+
+    #[serde(default)]
+    pub from: Option<String>,
+
+    #[serde(default)]
+    pub to: Option<String>,
+
+    #[serde(default)]
+    pub subject: Option<String>,
+}
+
+/// Filters for the events to fetch. All fields are optional.
+///
+/// See [Query Options](https://documentation.mailgun.com/en/latest/api-events.html#query-options)
+/// for the accepted values.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventsQuery {
+    /// Beginning of the time range, as an RFC 2822 date or Unix timestamp.
+    pub begin: Option<String>,
+
+    /// End of the time range, as an RFC 2822 date or Unix timestamp.
+    pub end: Option<String>,
+
+    /// Set to `true` to return the oldest events first.
+    pub ascending: Option<bool>,
+
+    /// Number of events per page, up to [`MAX_PAGE_LIMIT`](constant.MAX_PAGE_LIMIT.html).
+    pub limit: Option<u32>,
+
+    /// Type of event, such as `delivered` or `failed`. Supports MailGun's filter expressions.
+    pub event: Option<String>,
+
+    /// Recipient address of the message.
+    pub recipient: Option<String>,
+
+    /// ID of the message, without the surrounding angle brackets.
+    pub message_id: Option<String>,
+}
+
+impl EventsQuery {
+    /// Return the query as URL query parameters.
+    pub(crate) fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+
+        if let Some(begin) = &self.begin {
+            query.push(("begin", begin.clone()));
+        }
+
+        if let Some(end) = &self.end {
+            query.push(("end", end.clone()));
+        }
+
+        if let Some(ascending) = self.ascending {
+            query.push(("ascending", String::from(if ascending { "yes" } else { "no" })));
+        }
+
+        if let Some(limit) = self.limit {
+            query.push(("limit", limit.to_string()));
+        }
+
+        if let Some(event) = &self.event {
+            query.push(("event", event.clone()));
+        }
+
+        if let Some(recipient) = &self.recipient {
+            query.push(("recipient", recipient.clone()));
+        }
+
+        if let Some(message_id) = &self.message_id {
+            query.push(("message-id", message_id.clone()));
+        }
+
+        query
+    }
+}
+
+/// A page of events sent back from MailGun.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct EventsResponse {
+    /// Events of this page. Empty once all pages have been fetched.
+    pub items: Vec<Event>,
+
+    /// URLs of the surrounding pages.
+    pub paging: Paging,
+}
+
+/// URLs of the pages surrounding a page of results.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Paging {
+    pub first: String,
+    pub last: String,
+    pub next: String,
+    pub previous: String,
+}
+
+/// Final state of a message, as returned by [`Client::send_and_track`](../struct.Client.html#method.send_and_track).
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeliveryOutcome {
+    /// The message was delivered. Holds the `delivered` event.
+    Delivered(Event),
+
+    /// The message was rejected, or delivery failed permanently. Holds the `rejected` or
+    /// `failed` event.
+    Failed(Event),
+
+    /// Neither happened before the timeout elapsed.
+    TimedOut,
+}
+
+/// Return the outcome of the first event that ends the delivery of a message, if any.
+///
+/// Temporary failures are skipped, as MailGun keeps retrying the delivery after them.
+#[cfg(feature = "blocking")]
+fn delivery_outcome(events: &[Event]) -> Option<DeliveryOutcome> {
+    events.iter().find_map(|event| match event.event.as_str() {
+        "delivered" => Some(DeliveryOutcome::Delivered(event.clone())),
+        "rejected" => Some(DeliveryOutcome::Failed(event.clone())),
+        "failed" if event.severity.as_deref() != Some("temporary") => Some(DeliveryOutcome::Failed(event.clone())),
+        _ => None,
+    })
+}
+
+/// URL of the events endpoint of the domain of `client`.
+fn events_url(client: &crate::Client) -> String {
+    client.url_for(&format!("/v3/{}/events", client.domain()))
+}
+
+/// Get the first page of events matching `query` with an existing [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn get_events_with_client(client: &crate::Client, query: &EventsQuery) -> Result<EventsResponse, error::Error<'static>> {
+    let request = client
+        .request(reqwest::Method::GET, &events_url(client))
+        .query(&query.to_query());
+
+    client.send_request(request)
+}
+
+/// Iterate over all events matching `query` with an existing [`Client`](../struct.Client.html),
+/// following the pages of results.
+#[cfg(feature = "blocking")]
+pub fn iter_events_with_client<'c>(client: &'c crate::Client<'c>, query: &EventsQuery) -> crate::PagedIterator<'c, Event> {
+    let first = client
+        .request(reqwest::Method::GET, &events_url(client))
+        .query(&query.to_query());
+
+    crate::PagedIterator::new(client, first, |client, request| {
+        client
+            .send_request::<EventsResponse>(request)
+            .map(|page| (page.items, page.paging.next))
+    })
+}
+
+/// Collect up to `max` events matching `query` with an existing [`Client`](../struct.Client.html),
+/// following the pages of results until `max` events are gathered or the last page is reached.
+///
+/// When `query` has no `limit`, pages are requested no larger than `max`, so no more events
+/// than needed are fetched.
+#[cfg(feature = "blocking")]
+pub fn collect_events_with_client(client: &crate::Client, query: &EventsQuery, max: usize) -> Result<Vec<Event>, error::Error<'static>> {
+    if max == 0 {
+        return Ok(Vec::new());
+    }
+
+    iter_events_with_client(client, &collect_query(query, max))
+        .take(max)
+        .collect()
+}
+
+/// Query for collecting up to `max` events, with a page size no larger than needed.
+#[cfg(feature = "blocking")]
+fn collect_query(query: &EventsQuery, max: usize) -> EventsQuery {
+    let mut query = query.clone();
+
+    if query.limit.is_none() {
+        query.limit = Some(max.min(MAX_PAGE_LIMIT as usize) as u32);
+    }
+
+    query
+}
+
+/// Get the page of events at `url`, taken from the [`paging`](struct.Paging.html) of a previous
+/// page, with an existing [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn get_events_page_with_client(client: &crate::Client, url: &str) -> Result<EventsResponse, error::Error<'static>> {
+    let request = client.request(reqwest::Method::GET, url);
+
+    client.send_request(request)
+}
+
+/// Get all events of the message with `message_id` with an existing [`Client`](../struct.Client.html),
+/// following the pages of results.
+///
+/// `message_id` may include the angle brackets of the `Message-Id` header, as returned when
+/// sending a message.
+#[cfg(feature = "blocking")]
+pub fn get_events_for_message_with_client(client: &crate::Client, message_id: &str) -> Result<Vec<Event>, error::Error<'static>> {
+    iter_events_with_client(client, &message_events_query(message_id)).collect()
+}
+
+/// Query for the events of the message with `message_id`, stripped of its angle brackets.
+#[cfg(feature = "blocking")]
+fn message_events_query(message_id: &str) -> EventsQuery {
+    EventsQuery {
+        message_id: Some(message_id.trim_start_matches('<').trim_end_matches('>').to_string()),
+        ..Default::default()
+    }
+}
+
+/// Poll the events of the message with `message_id` with an existing [`Client`](../struct.Client.html)
+/// until it is delivered, fails, or `timeout` elapses.
+///
+/// Events are polled every [`TRACK_POLL_INTERVAL`](constant.TRACK_POLL_INTERVAL.html), as they
+/// can take a while to appear after a message is sent. For messages with several recipients,
+/// the outcome of the first recipient to be delivered or fail is returned.
+#[cfg(feature = "blocking")]
+pub fn track_delivery_with_client(client: &crate::Client, message_id: &str, timeout: Duration) -> Result<DeliveryOutcome, error::Error<'static>> {
+    let deadline = Instant::now() + timeout;
+
+    let query = EventsQuery {
+        ascending: Some(true),
+        event: Some(String::from("delivered OR failed OR rejected")),
+        ..message_events_query(message_id)
+    };
+
+    loop {
+        let mut page = get_events_with_client(client, &query)?;
+
+        while !page.items.is_empty() {
+            if let Some(outcome) = delivery_outcome(&page.items) {
+                return Ok(outcome);
+            }
+
+            page = get_events_page_with_client(client, &page.paging.next)?;
+        }
+
+        let now = Instant::now();
+
+        if now >= deadline {
+            return Ok(DeliveryOutcome::TimedOut);
+        }
+
+        thread::sleep(TRACK_POLL_INTERVAL.min(deadline - now));
+    }
+}
+
+/// Stream all events matching `query` with an existing [`Client`](../struct.Client.html),
+/// following the pages of results.
+///
+/// Pages are only fetched once the events of the previous page have been consumed. The stream
+/// ends after the last page, or after the first error.
+#[cfg(feature = "async")]
+pub fn events_stream_with_client(client: &crate::Client, query: EventsQuery) -> impl futures_util::stream::Stream<Item = Result<Event, error::Error<'static>>> {
+    use std::collections::VecDeque;
+
+    struct State {
+        requester: crate::client::AsyncRequester,
+        next: Option<reqwest::RequestBuilder>,
+        buffer: VecDeque<Event>,
+    }
+
+    let requester = client.async_requester();
+    let first = requester
+        .request(reqwest::Method::GET, &events_url(client))
+        .query(&query.to_query());
+
+    let state = State { requester, next: Some(first), buffer: VecDeque::new() };
+
+    futures_util::stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(event) = state.buffer.pop_front() {
+                return Some((Ok(event), state));
+            }
+
+            let request = state.next.take()?;
+
+            match state.requester.send::<EventsResponse>(request).await {
+                Ok(page) => {
+                    if page.items.is_empty() {
+                        return None;
+                    }
+
+                    state.buffer.extend(page.items);
+                    state.next = Some(state.requester.request(reqwest::Method::GET, &page.paging.next));
+                },
+                Err(error) => return Some((Err(error), state)),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_response_deserialize() {
+        let body = r#"{
+            "items": [
+                {
+                    "event": "delivered",
+                    "id": "W3X4JOhFT-OZidZGKKr9iA",
+                    "timestamp": 1529692199.626182,
+                    "log-level": "info",
+                    "recipient": "alice@example.com",
+                    "tags": ["newsletter"],
+                    "storage": {
+                        "url": "https://se.api.mailgun.net/v3/domains/example.com/messages/message_key",
+                        "key": "message_key"
+                    },
+                    "envelope": { "transport": "smtp" },
+                    "delivery-status": {
+                        "tls": true,
+                        "mx-host": "smtp-in.example.com",
+                        "code": 250,
+                        "description": "",
+                        "session-seconds": 0.4331989288330078,
+                        "utf8": true,
+                        "attempt-no": 1,
+                        "message": "OK",
+                        "certificate-verified": true
+                    },
+                    "message": {
+                        "headers": {
+                            "to": "Alice <alice@example.com>",
+                            "message-id": "20180622182958.1.48906CB188F1A454@example.com",
+                            "from": "Bob <bob@example.com>",
+                            "subject": "Hello"
+                        },
+                        "attachments": [],
+                        "size": 586
+                    }
+                },
+                {
+                    "event": "failed",
+                    "id": "czsjqFATSlC3QtAK-C80nw",
+                    "timestamp": 1529701969.818328,
+                    "log-level": "error",
+                    "severity": "permanent",
+                    "reason": "suppress-bounce",
+                    "recipient": "carol@example.com"
+                }
+            ],
+            "paging": {
+                "previous": "https://api.mailgun.net/v3/example.com/events/W3siYiI6...",
+                "first": "https://api.mailgun.net/v3/example.com/events/W3siYiI6...",
+                "last": "https://api.mailgun.net/v3/example.com/events/W3siYiI6...",
+                "next": "https://api.mailgun.net/v3/example.com/events/W3siYiI6...next"
+            }
+        }"#;
+
+        let response = serde_json::from_str::<EventsResponse>(body).unwrap();
+
+        assert_eq!(2, response.items.len());
+        assert_eq!("delivered", response.items[0].event);
+        assert_eq!(vec![String::from("newsletter")], response.items[0].tags);
+        assert_eq!(
+            Some(String::from("20180622182958.1.48906CB188F1A454@example.com")),
+            response.items[0].message.as_ref().unwrap().headers.message_id
+        );
+        assert_eq!(Some("message_key"), response.items[0].storage_key());
+        assert_eq!(
+            Some("https://se.api.mailgun.net/v3/domains/example.com/messages/message_key"),
+            response.items[0].storage_url()
+        );
+        assert_eq!(None, response.items[1].storage_url());
+        assert_eq!(Some(true), response.items[0].used_tls());
+        assert_eq!(Some(true), response.items[0].delivery_status.as_ref().unwrap().certificate_verified);
+        assert_eq!(Some(250), response.items[0].delivery_status.as_ref().unwrap().code);
+        assert_eq!(Some("smtp-in.example.com"), response.items[0].delivery_status.as_ref().unwrap().mx_host.as_deref());
+        assert_eq!(None, response.items[1].used_tls());
+        assert_eq!(Some(String::from("permanent")), response.items[1].severity);
+        assert_eq!(None, response.items[1].message);
+        assert!(response.paging.next.ends_with("next"));
+    }
+
+    #[test]
+    fn events_query_to_query() {
+        let mut query = EventsQuery::default();
+        assert!(query.to_query().is_empty());
+
+        let limited = EventsQuery { limit: Some(100), ..Default::default() };
+        assert_eq!(vec![("limit", String::from("100"))], limited.to_query());
+
+        query.ascending = Some(true);
+        query.limit = Some(50);
+        query.event = Some(String::from("failed OR rejected"));
+        query.message_id = Some(String::from("1234@example.com"));
+
+        assert_eq!(vec![
+            ("ascending", String::from("yes")),
+            ("limit", String::from("50")),
+            ("event", String::from("failed OR rejected")),
+            ("message-id", String::from("1234@example.com")),
+        ], query.to_query());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn events_collect_query() {
+        let query = EventsQuery { event: Some(String::from("failed")), ..Default::default() };
+
+        assert_eq!(Some(25), collect_query(&query, 25).limit);
+        assert_eq!(Some(MAX_PAGE_LIMIT), collect_query(&query, 10_000).limit);
+        assert_eq!(Some(String::from("failed")), collect_query(&query, 25).event);
+
+        let query = EventsQuery { limit: Some(50), ..Default::default() };
+        assert_eq!(Some(50), collect_query(&query, 10).limit);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn events_message_events_query() {
+        let query = message_events_query("<1234@example.com>");
+
+        assert_eq!(vec![("message-id", String::from("1234@example.com"))], query.to_query());
+        assert_eq!(query.to_query(), message_events_query("1234@example.com").to_query());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn events_delivery_outcome() {
+        let event = |event: &str, severity: Option<&str>| {
+            let json = serde_json::json!({ "id": "id", "event": event, "timestamp": 0.0, "severity": severity });
+
+            serde_json::from_value::<Event>(json).unwrap()
+        };
+
+        assert_eq!(None, delivery_outcome(&[]));
+        assert_eq!(None, delivery_outcome(&[event("failed", Some("temporary"))]));
+
+        let delivered = event("delivered", None);
+        assert_eq!(
+            Some(DeliveryOutcome::Delivered(delivered.clone())),
+            delivery_outcome(&[event("failed", Some("temporary")), delivered])
+        );
+
+        let failed = event("failed", Some("permanent"));
+        assert_eq!(Some(DeliveryOutcome::Failed(failed.clone())), delivery_outcome(&[failed]));
+
+        let rejected = event("rejected", None);
+        assert_eq!(Some(DeliveryOutcome::Failed(rejected.clone())), delivery_outcome(&[rejected]));
+    }
+}
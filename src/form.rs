@@ -0,0 +1,182 @@
+//! Encoding of `multipart/form-data` request bodies.
+//!
+//! Unlike the [`multipart`](https://docs.rs/multipart) crate, this allows setting extra headers
+//! on each part, such as the `Content-ID` of inline attachments.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A `multipart/form-data` body being built in memory.
+pub(crate) struct MultipartForm {
+    boundary: String,
+    body: Vec<u8>,
+    omitted: u64,
+}
+
+impl MultipartForm {
+    /// Create an empty form with a random boundary.
+    pub(crate) fn new() -> MultipartForm {
+        MultipartForm {
+            boundary: random_boundary(),
+            body: Vec::new(),
+            omitted: 0,
+        }
+    }
+
+    /// Get the boundary separating the parts of the form.
+    pub(crate) fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Add a text field.
+    pub(crate) fn add_text(&mut self, name: &str, value: &str) {
+        self.start_part(name, None);
+        self.body.extend_from_slice(b"\r\n");
+        self.body.extend_from_slice(value.as_bytes());
+        self.body.extend_from_slice(b"\r\n");
+    }
+
+    /// Add a file field, with any extra part headers.
+    ///
+    /// Fails if the content type or a header contains a line break, which would inject headers
+    /// or a boundary into the body.
+    pub(crate) fn add_file(&mut self, name: &str, filename: &str, content_type: &str, headers: &[(&str, &str)], data: &[u8]) -> io::Result<()> {
+        self.start_file(name, filename, content_type, headers)?;
+        self.body.extend_from_slice(data);
+        self.body.extend_from_slice(b"\r\n");
+
+        Ok(())
+    }
+
+    /// Add a file field of `length` bytes without its contents, to measure the form with
+    /// [`finish_length`](#method.finish_length) instead of encoding it.
+    pub(crate) fn add_file_length(&mut self, name: &str, filename: &str, content_type: &str, headers: &[(&str, &str)], length: u64) -> io::Result<()> {
+        self.start_file(name, filename, content_type, headers)?;
+        self.omitted += length;
+        self.body.extend_from_slice(b"\r\n");
+
+        Ok(())
+    }
+
+    /// Close the form and return the encoded body.
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        self.body.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+
+        self.body
+    }
+
+    /// Close the form and return the length of the encoded body, including the files added
+    /// with [`add_file_length`](#method.add_file_length).
+    pub(crate) fn finish_length(self) -> u64 {
+        let omitted = self.omitted;
+
+        self.finish().len() as u64 + omitted
+    }
+
+    fn start_file(&mut self, name: &str, filename: &str, content_type: &str, headers: &[(&str, &str)]) -> io::Result<()> {
+        check_header("Content-Type", content_type)?;
+
+        for (key, value) in headers {
+            check_header(key, value)?;
+        }
+
+        self.start_part(name, Some(filename));
+        self.push_header("Content-Type", content_type);
+
+        for (key, value) in headers {
+            self.push_header(key, value);
+        }
+
+        self.body.extend_from_slice(b"\r\n");
+
+        Ok(())
+    }
+
+    fn start_part(&mut self, name: &str, filename: Option<&str>) {
+        self.body.extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+
+        let disposition = match filename {
+            Some(filename) => format!("form-data; name=\"{}\"; filename=\"{}\"", escape(name), escape(filename)),
+            None => format!("form-data; name=\"{}\"", escape(name)),
+        };
+
+        self.push_header("Content-Disposition", &disposition);
+    }
+
+    fn push_header(&mut self, key: &str, value: &str) {
+        self.body.extend_from_slice(format!("{}: {}\r\n", key, value).as_bytes());
+    }
+}
+
+/// Return an error if a part header cannot be written as a single line.
+pub(crate) fn check_header(key: &str, value: &str) -> io::Result<()> {
+    if key.contains(['\r', '\n', ':']) || value.contains(['\r', '\n']) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("the {} header of a part cannot contain line breaks", key.trim())));
+    }
+
+    Ok(())
+}
+
+/// Escape quotes and line breaks in a quoted header parameter.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace(['\r', '\n'], " ")
+}
+
+/// Generate a boundary that is unlikely to appear in the body.
+pub(crate) fn random_boundary() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+
+    let mut first = RandomState::new().build_hasher();
+    first.write_u128(nanos);
+
+    let mut second = RandomState::new().build_hasher();
+    second.write_u64(first.finish());
+
+    format!("mailgun-sdk-{:016x}{:016x}", first.finish(), second.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multipart_form_encode() {
+        let mut form = MultipartForm::new();
+        let boundary = form.boundary().to_string();
+
+        form.add_text("subject", "Hello");
+        form.add_file("inline", "logo.png", "image/png", &[("Content-ID", "<logo>")], b"PNG").unwrap();
+
+        let body = String::from_utf8(form.finish()).unwrap();
+
+        assert_eq!(format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"subject\"\r\n\r\nHello\r\n\
+             --{b}\r\nContent-Disposition: form-data; name=\"inline\"; filename=\"logo.png\"\r\n\
+             Content-Type: image/png\r\nContent-ID: <logo>\r\n\r\nPNG\r\n--{b}--\r\n",
+            b = boundary,
+        ), body);
+    }
+
+    #[test]
+    fn multipart_form_rejects_header_injection() {
+        let mut form = MultipartForm::new();
+
+        assert!(form.add_file("inline", "logo.png", "image/png", &[("Content-ID", "<logo>\r\nX-Injected: yes")], b"PNG").is_err());
+        assert!(form.add_file("inline", "logo.png", "image/png\r\n\r\n--boundary", &[], b"PNG").is_err());
+
+        let body = String::from_utf8(form.finish()).unwrap();
+
+        assert!(!body.contains("X-Injected"));
+        assert!(!body.contains("logo.png"));
+    }
+
+    #[test]
+    fn multipart_form_random_boundary() {
+        assert_ne!(MultipartForm::new().boundary(), MultipartForm::new().boundary());
+    }
+}
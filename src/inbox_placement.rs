@@ -0,0 +1,174 @@
+//! Model for inbox placement tests, which send a message to a seed list of mailboxes and report
+//! where it landed.
+//!
+//! Full API documentation: [https://documentation.mailgun.com/en/latest/api-inbox-placement.html](https://documentation.mailgun.com/en/latest/api-inbox-placement.html)
+//!
+//! Tests run asynchronously: create a test, then fetch its result once the seed mailboxes have
+//! received the message. Note that the Inbox Placement API lives under the `/v4` base path,
+//! rather than `/v3` like most of the other APIs.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "blocking")] {
+//! use mailgun_sdk::Client as MailGunClient;
+//! use mailgun_sdk::inbox_placement::InboxPlacementTest;
+//!
+//! let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+//!
+//! let test = InboxPlacementTest {
+//!     from: "sender@YOUR_DOMAIN.com",
+//!     subject: "Seed test",
+//!     html: "<p>Hello</p>",
+//! };
+//!
+//! let created = client.create_inbox_placement_test(&test).unwrap();
+//!
+//! // Later, once the seed mailboxes have received the message.
+//! let result = client.get_inbox_placement_result(&created.tid).unwrap();
+//!
+//! println!("inbox: {}, spam: {}, missing: {}", result.inbox, result.spam, result.missing);
+//! # }
+//! ```
+
+#[cfg(feature = "blocking")]
+use crate::error;
+use serde::{Deserialize, Serialize};
+
+/// Message to send to the seed list of an inbox placement test.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InboxPlacementTest<'a> {
+    /// Sender of the message, from a domain of the account.
+    pub from: &'a str,
+
+    /// Subject of the message.
+    pub subject: &'a str,
+
+    /// HTML body of the message.
+    pub html: &'a str,
+}
+
+/// Response sent back from MailGun after creating an inbox placement test.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct CreateInboxPlacementTestResponse {
+    /// ID of the test, used to fetch its result.
+    pub tid: String,
+
+    /// Human readable description of the result.
+    #[serde(default)]
+    pub message: String,
+}
+
+/// Where the messages of an inbox placement test landed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InboxPlacementResult {
+    /// Number of seed mailboxes that received the message in their inbox.
+    pub inbox: u32,
+
+    /// Number of seed mailboxes that received the message in their spam folder.
+    pub spam: u32,
+
+    /// Number of seed mailboxes that have not received the message.
+    pub missing: u32,
+
+    /// Counts for each mailbox provider.
+    pub by_provider: Vec<ProviderPlacement>,
+}
+
+/// Where the messages of an inbox placement test landed for one mailbox provider.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct ProviderPlacement {
+    /// Mailbox provider, such as `gmail.com`.
+    pub provider: String,
+
+    /// Number of seed mailboxes that received the message in their inbox.
+    #[serde(default)]
+    pub inbox: u32,
+
+    /// Number of seed mailboxes that received the message in their spam folder.
+    #[serde(default)]
+    pub spam: u32,
+
+    /// Number of seed mailboxes that have not received the message.
+    #[serde(default)]
+    pub missing: u32,
+}
+
+/// Response sent back from MailGun when fetching the counters of a test.
+#[derive(Debug, Deserialize)]
+struct CountersResponse {
+    counters: Vec<ProviderPlacement>,
+}
+
+impl From<CountersResponse> for InboxPlacementResult {
+    fn from(response: CountersResponse) -> InboxPlacementResult {
+        let mut result = InboxPlacementResult::default();
+
+        for provider in &response.counters {
+            result.inbox += provider.inbox;
+            result.spam += provider.spam;
+            result.missing += provider.missing;
+        }
+
+        result.by_provider = response.counters;
+
+        result
+    }
+}
+
+/// Create an inbox placement test with an existing [`Client`](../struct.Client.html), sending
+/// `test` to the seed list of the account.
+#[cfg(feature = "blocking")]
+pub fn create_inbox_placement_test_with_client(client: &crate::Client, test: &InboxPlacementTest) -> Result<CreateInboxPlacementTestResponse, error::Error<'static>> {
+    let request = client
+        .request(reqwest::Method::POST, &client.url_for("/v4/inbox/tests"))
+        .form(test);
+
+    client.send_request(request)
+}
+
+/// Get where the messages of the inbox placement test with `id` landed, with an existing
+/// [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn get_inbox_placement_result_with_client(client: &crate::Client, id: &str) -> Result<InboxPlacementResult, error::Error<'static>> {
+    let url = client.url_for(&format!("/v4/inbox/tests/{}/counters", id));
+
+    let request = client.request(reqwest::Method::GET, &url);
+
+    client
+        .send_request::<CountersResponse>(request)
+        .map(InboxPlacementResult::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inbox_placement_result_from_counters() {
+        let body = r#"{
+            "counters": [
+                { "provider": "gmail.com", "inbox": 3, "spam": 1, "missing": 0 },
+                { "provider": "yahoo.com", "inbox": 1, "spam": 0, "missing": 2 },
+                { "provider": "outlook.com" }
+            ]
+        }"#;
+
+        let result = InboxPlacementResult::from(serde_json::from_str::<CountersResponse>(body).unwrap());
+
+        assert_eq!(4, result.inbox);
+        assert_eq!(1, result.spam);
+        assert_eq!(2, result.missing);
+        assert_eq!(3, result.by_provider.len());
+        assert_eq!(ProviderPlacement { provider: String::from("outlook.com"), ..Default::default() }, result.by_provider[2]);
+    }
+
+    #[test]
+    fn create_inbox_placement_test_response_deserialize() {
+        let body = r#"{ "tid": "5e3b4ad1f8a1b2c3d4e5f6a7", "message": "Test created" }"#;
+
+        let response = serde_json::from_str::<CreateInboxPlacementTestResponse>(body).unwrap();
+
+        assert_eq!("5e3b4ad1f8a1b2c3d4e5f6a7", response.tid);
+    }
+}
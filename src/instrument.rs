@@ -0,0 +1,88 @@
+//! Optional instrumentation of the requests sent to MailGun.
+//!
+//! When the `tracing` feature is enabled, each request is wrapped in a `mailgun.request` span
+//! recording the endpoint, domain, status code, and elapsed time. The API key and message body
+//! are never recorded. Without the feature, these helpers do nothing.
+
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+/// Span covering a single request to MailGun.
+pub(crate) struct RequestSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+
+    #[cfg(feature = "tracing")]
+    started: Instant,
+}
+
+impl RequestSpan {
+    /// Start a span for a request to `endpoint` on behalf of `domain`.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn new(endpoint: &str, domain: &str) -> RequestSpan {
+        let span = tracing::info_span!(
+            "mailgun.request",
+            endpoint = endpoint,
+            domain = domain,
+            status = tracing::field::Empty,
+            elapsed_ms = tracing::field::Empty,
+        );
+
+        RequestSpan { span, started: Instant::now() }
+    }
+
+    /// Start a span for a request to `endpoint` on behalf of `domain`.
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn new(_endpoint: &str, _domain: &str) -> RequestSpan {
+        RequestSpan {}
+    }
+
+    /// Run `f` within the span.
+    #[cfg(all(feature = "blocking", feature = "tracing"))]
+    pub(crate) fn in_scope<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        self.span.in_scope(f)
+    }
+
+    /// Run `f` within the span.
+    #[cfg(all(feature = "blocking", not(feature = "tracing")))]
+    pub(crate) fn in_scope<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        f()
+    }
+
+    /// Poll `future` within the span.
+    #[cfg(all(feature = "async", feature = "tracing"))]
+    pub(crate) async fn instrument<F>(&self, future: F) -> F::Output
+    where
+        F: Future,
+    {
+        tracing::Instrument::instrument(future, self.span.clone()).await
+    }
+
+    /// Poll `future` within the span.
+    #[cfg(all(feature = "async", not(feature = "tracing")))]
+    pub(crate) async fn instrument<F>(&self, future: F) -> F::Output
+    where
+        F: Future,
+    {
+        future.await
+    }
+
+    /// Record the status code of the response and the time elapsed since the span started.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn record_status(&self, status: u16) {
+        self.span.record("status", status);
+        self.span.record("elapsed_ms", self.started.elapsed().as_millis() as u64);
+    }
+
+    /// Record the status code of the response and the time elapsed since the span started.
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn record_status(&self, _status: u16) {}
+}
@@ -7,34 +7,96 @@
 //! ### Send Message Example
 //!
 //! ```no_run
+//! # #[cfg(feature = "blocking")] {
 //! use mailgun_sdk::Client as MailGunClient;
-//! use mailgun_sdk::message::{Email, Message, MessageBuilder};
+//! use mailgun_sdk::message::{self, Email, Message, MessageBuilder};
 //!
 //! let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
 //!
-//! let from = Email::new(None, "from@host.com");
-//! let to = vec![
-//!     Email::new(None, "to1@host.com"),
-//!     Email::new(None, "to2@host.com"),
-//! ];
+//! let from = Email::from("from@host.com");
+//! let to = message::emails(&["to1@host.com", "to2@host.com"]);
 //!
 //! let mut builder = MessageBuilder::new("Subject Line", &from, &to);
 //! builder.html(Some("<HTML><h1>Message Body</h1></HTML>"));
 //!
 //! client.send_message(builder.get_message()).unwrap();
+//! # }
 //! ```
+//!
+//! ### Features
+//!
+//! - `blocking`: the blocking methods of [`Client`](struct.Client.html), which cover every API
+//!   of the library, and the `*_with_client` functions of each module. Enabled by default.
+//! - `async`: send messages with [`Client::send_message_async`](struct.Client.html#method.send_message_async)
+//!   and stream events with [`Client::events_stream`](struct.Client.html#method.events_stream).
+//!   Disabled by default.
+//! - `send-mime`: send messages as raw MIME built by the library with
+//!   [`Client::send_mime`](struct.Client.html#method.send_mime), encoding each attachment with
+//!   its [`TransferEncoding`](message/enum.TransferEncoding.html). Requires `blocking`.
+//!   Disabled by default.
+//! - `test-util`: build a [`Client`](struct.Client.html) with a
+//!   [`MockTransport`](mock/struct.MockTransport.html) that captures requests instead of
+//!   sending them, for unit tests. Disabled by default.
+//! - `tracing`: instrument each request with a [`tracing`](https://docs.rs/tracing) span
+//!   recording the endpoint, domain, status code, and elapsed time. Disabled by default.
+//!
+//! At least one of `blocking` and `async` must be enabled, and both can be enabled together.
+//! Applications that only use the async methods can drop the blocking client of `reqwest`, and
+//! the thread it starts, with:
+//!
+//! ```toml
+//! [dependencies]
+//! mailgun-sdk = { version = "0.1", default-features = false, features = ["async"] }
+//! ```
+//!
+//! | Features              | Blocking methods | Async methods |
+//! |-----------------------|------------------|---------------|
+//! | `blocking` (default)  | Yes              | No            |
+//! | `async`               | No               | Yes           |
+//! | `blocking`, `async`   | Yes              | Yes           |
+//!
+//! ### Breaking Changes
+//!
+//! - [`Client`](struct.Client.html) can own its API key and domain, so it can be stored in the
+//!   shared state of a web framework. [`Client::api_key`](struct.Client.html#method.api_key)
+//!   and [`Client::domain`](struct.Client.html#method.domain) therefore return strings borrowed
+//!   from the client instead of `&'a str`. Copy them with `to_string` to keep them after the
+//!   client is dropped or moved.
 
+#[cfg(not(any(feature = "blocking", feature = "async")))]
+compile_error!("mailgun-sdk requires the `blocking` feature, the `async` feature, or both");
+
+extern crate mime_guess;
 extern crate multipart;
 extern crate reqwest;
 extern crate serde;
 extern crate serde_json;
 extern crate serde_urlencoded;
 
+pub mod account;
 mod client;
+pub mod credential;
+pub mod domain;
 mod error;
+pub mod events;
+mod form;
+pub mod inbox_placement;
+mod instrument;
 pub mod message;
+#[cfg(feature = "send-mime")]
+mod mime;
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock;
+#[cfg(feature = "blocking")]
+mod paging;
+mod rate_limit;
+pub mod subaccount;
+pub mod suppression;
+pub mod util;
+pub mod webhook;
 
-const API_BASE_PATH: &'static str = "https://api.mailgun.net/v3";
-
-pub use client::Client;
+pub use client::{Auth, Client, ClientBuilder, RedirectPolicy, Region, RequestInfo, RequestLogger};
 pub use error::Error;
+#[cfg(feature = "blocking")]
+pub use paging::PagedIterator;
+pub use rate_limit::RateLimiter;
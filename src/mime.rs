@@ -0,0 +1,292 @@
+//! Encoding of raw MIME messages, sent to the `messages.mime` endpoint of MailGun.
+//!
+//! Unlike `multipart/form-data` uploads, where MailGun builds the message and encodes the
+//! attachments itself, a raw MIME message is delivered as is, so every part is encoded here
+//! with a transfer encoding safe for any mail server along the way.
+
+use crate::form::{check_header, random_boundary};
+use crate::message::TransferEncoding;
+use std::io;
+
+/// Longest line allowed in a message, without the line break, as set by RFC 5322.
+const MAX_LINE_LENGTH: usize = 998;
+
+/// Length of the lines of base64-encoded data, as set by RFC 2045.
+const BASE64_LINE_LENGTH: usize = 76;
+
+/// Number of bytes encoded in each encoded word of a header, so each word stays below the 75
+/// characters allowed by RFC 2047.
+const ENCODED_WORD_BYTES: usize = 45;
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Part of a MIME message, with its headers and encoded body.
+pub(crate) struct Part {
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Part {
+    /// Create a text part of `content_type`, such as `text/plain`, holding `text` as base64
+    /// encoded UTF-8.
+    pub(crate) fn text(content_type: &str, text: &str) -> Part {
+        Part {
+            headers: vec![
+                (String::from("Content-Type"), format!("{}; charset=utf-8", content_type)),
+                (String::from("Content-Transfer-Encoding"), String::from("base64")),
+            ],
+            body: base64(text.as_bytes()).into_bytes(),
+        }
+    }
+
+    /// Create a file part with a `disposition` of `attachment` or `inline`, encoding `data`
+    /// with `encoding`.
+    ///
+    /// Fails if `data` cannot be sent with `encoding`, or if a header contains a line break.
+    pub(crate) fn file(disposition: &str, name: &str, content_type: &str, content_id: Option<&str>, encoding: TransferEncoding, data: &[u8]) -> io::Result<Part> {
+        let body = match encoding {
+            TransferEncoding::Base64 => base64(data).into_bytes(),
+            TransferEncoding::SevenBit => check_lines(data, false).map(|_| data.to_vec())?,
+            TransferEncoding::EightBit => check_lines(data, true).map(|_| data.to_vec())?,
+        };
+
+        let name = quote(name);
+
+        let mut headers = vec![
+            (String::from("Content-Type"), format!("{}; name={}", content_type, name)),
+            (String::from("Content-Disposition"), format!("{}; filename={}", disposition, name)),
+            (String::from("Content-Transfer-Encoding"), String::from(encoding.as_str())),
+        ];
+
+        if let Some(content_id) = content_id {
+            headers.push((String::from("Content-ID"), String::from(content_id)));
+        }
+
+        for (key, value) in &headers {
+            check_header(key, value)?;
+        }
+
+        Ok(Part { headers, body })
+    }
+
+    /// Combine `parts` into a single `multipart/{subtype}` part.
+    pub(crate) fn multipart(subtype: &str, parts: Vec<Part>) -> Part {
+        let boundary = random_boundary();
+
+        let mut body = Vec::new();
+
+        for part in parts {
+            body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+            part.write(&mut body);
+            body.extend_from_slice(b"\r\n");
+        }
+
+        body.extend_from_slice(format!("--{}--", boundary).as_bytes());
+
+        Part {
+            headers: vec![(String::from("Content-Type"), format!("multipart/{}; boundary=\"{}\"", subtype, boundary))],
+            body,
+        }
+    }
+
+    /// Encode the part as a whole message, with the message `headers`, such as `From` and
+    /// `Subject`, in front of its own.
+    ///
+    /// Fails if a header contains a line break other than the folding of
+    /// [`encode_words`](fn.encode_words.html), which would inject headers into the message.
+    pub(crate) fn into_message(self, headers: &[(String, String)]) -> io::Result<Vec<u8>> {
+        let mut message = Vec::new();
+
+        for (key, value) in headers {
+            check_header(key, &value.replace("\r\n ", " "))?;
+
+            message.extend_from_slice(format!("{}: {}\r\n", key, value).as_bytes());
+        }
+
+        message.extend_from_slice(b"MIME-Version: 1.0\r\n");
+        self.write(&mut message);
+        message.extend_from_slice(b"\r\n");
+
+        Ok(message)
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        for (key, value) in &self.headers {
+            out.extend_from_slice(format!("{}: {}\r\n", key, value).as_bytes());
+        }
+
+        out.extend_from_slice(b"\r\n");
+        out.extend_from_slice(&self.body);
+    }
+}
+
+/// Encode an unstructured header value, such as a subject, as RFC 2047 encoded words when it is
+/// not plain ASCII. The words are folded onto separate lines, so long values stay within the
+/// line length limit.
+pub(crate) fn encode_words(value: &str) -> String {
+    if value.is_ascii() {
+        return value.to_string();
+    }
+
+    let mut words = Vec::new();
+    let mut start = 0;
+
+    for (index, c) in value.char_indices() {
+        if index + c.len_utf8() - start > ENCODED_WORD_BYTES {
+            words.push(encode_word(&value[start..index]));
+            start = index;
+        }
+    }
+
+    words.push(encode_word(&value[start..]));
+
+    words.join("\r\n ")
+}
+
+fn encode_word(value: &str) -> String {
+    format!("=?utf-8?B?{}?=", base64(value.as_bytes()))
+}
+
+/// Quote a header parameter, such as a file name. Names that are not plain ASCII are sent as a
+/// single encoded word, which mail clients decode in parameters too.
+fn quote(value: &str) -> String {
+    let value = if value.is_ascii() {
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    } else {
+        encode_word(value)
+    };
+
+    format!("\"{}\"", value)
+}
+
+/// Encode `data` as base64, in lines of 76 characters.
+fn base64(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len() * 4 / 3 + 4);
+    let mut line_length = 0;
+
+    for chunk in data.chunks(3) {
+        if line_length == BASE64_LINE_LENGTH {
+            encoded.push_str("\r\n");
+            line_length = 0;
+        }
+
+        line_length += 4;
+
+        let bytes = [chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let group = u32::from(bytes[0]) << 16 | u32::from(bytes[1]) << 8 | u32::from(bytes[2]);
+
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(char::from(BASE64_ALPHABET[(group >> (18 - 6 * index) & 0x3f) as usize]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+/// Return an error if `data` cannot be sent without encoding: it must be made of lines of at
+/// most 998 bytes ending with CRLF, without NUL bytes, and only of ASCII unless `allow_8bit`.
+fn check_lines(data: &[u8], allow_8bit: bool) -> io::Result<()> {
+    let invalid = |message: &str| Err(io::Error::new(io::ErrorKind::InvalidInput, format!("attachment data {}, use the base64 transfer encoding", message)));
+
+    let mut line_length = 0;
+
+    for (index, byte) in data.iter().enumerate() {
+        match byte {
+            0 => return invalid("contains a NUL byte"),
+            b'\r' if data.get(index + 1) != Some(&b'\n') => return invalid("contains a bare carriage return"),
+            b'\n' if index == 0 || data[index - 1] != b'\r' => return invalid("contains a bare line feed"),
+            b'\r' | b'\n' => line_length = 0,
+            128..=255 if !allow_8bit => return invalid("is not ASCII"),
+            _ => {
+                line_length += 1;
+
+                if line_length > MAX_LINE_LENGTH {
+                    return invalid("has a line longer than 998 bytes");
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_encoding() {
+        assert_eq!("", base64(b""));
+        assert_eq!("Zg==", base64(b"f"));
+        assert_eq!("Zm8=", base64(b"fo"));
+        assert_eq!("Zm9v", base64(b"foo"));
+        assert_eq!("Zm9vYmFy", base64(b"foobar"));
+        assert_eq!("AP/+", base64(&[0x00, 0xff, 0xfe]));
+
+        let encoded = base64(&[0xaa; 120]);
+        let lines = encoded.split("\r\n").collect::<Vec<&str>>();
+
+        assert_eq!(vec![76, 76, 8], lines.iter().map(|line| line.len()).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn encode_header_words() {
+        assert_eq!("Monthly news", encode_words("Monthly news"));
+        assert_eq!("=?utf-8?B?Q2Fmw6k=?=", encode_words("Café"));
+
+        let encoded = encode_words(&"é".repeat(40));
+
+        assert_eq!(2, encoded.split("\r\n ").count());
+        assert!(encoded.split("\r\n ").all(|word| word.len() <= 75));
+    }
+
+    #[test]
+    fn file_part_transfer_encodings() {
+        let part = Part::file("attachment", "report.bin", "application/octet-stream", None, TransferEncoding::Base64, &[0x00, 0xff, 0xfe]).unwrap();
+
+        let mut body = Vec::new();
+        part.write(&mut body);
+        let body = String::from_utf8(body).unwrap();
+
+        assert!(body.contains("Content-Disposition: attachment; filename=\"report.bin\"\r\n"));
+        assert!(body.contains("Content-Transfer-Encoding: base64\r\n\r\nAP/+"));
+
+        assert!(Part::file("attachment", "notes.txt", "text/plain", None, TransferEncoding::SevenBit, b"plain text\r\n").is_ok());
+        assert!(Part::file("attachment", "notes.txt", "text/plain", None, TransferEncoding::EightBit, "café\r\n".as_bytes()).is_ok());
+
+        let rejected: &[(TransferEncoding, &[u8])] = &[
+            (TransferEncoding::SevenBit, "café".as_bytes()),
+            (TransferEncoding::EightBit, b"nul\0byte"),
+            (TransferEncoding::EightBit, b"bare\nline feed"),
+            (TransferEncoding::EightBit, b"bare\rcarriage return"),
+            (TransferEncoding::EightBit, &[b'a'; MAX_LINE_LENGTH + 1]),
+        ];
+
+        for (encoding, data) in rejected {
+            match Part::file("attachment", "notes.txt", "text/plain", None, *encoding, data) {
+                Err(error) => assert_eq!(io::ErrorKind::InvalidInput, error.kind()),
+                Ok(_) => panic!("Expected {:?} to reject {:?}", encoding, String::from_utf8_lossy(data)),
+            }
+        }
+    }
+
+    #[test]
+    fn multipart_message() {
+        let body = Part::multipart("alternative", vec![Part::text("text/plain", "Hello"), Part::text("text/html", "<p>Hello</p>")]);
+
+        let headers = vec![(String::from("Subject"), encode_words("Café"))];
+        let message = String::from_utf8(body.into_message(&headers).unwrap()).unwrap();
+
+        assert!(message.starts_with("Subject: =?utf-8?B?Q2Fmw6k=?=\r\nMIME-Version: 1.0\r\nContent-Type: multipart/alternative; boundary=\""));
+        assert!(message.contains("Content-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: base64\r\n\r\nSGVsbG8=\r\n"));
+        assert!(message.contains("Content-Type: text/html; charset=utf-8\r\n"));
+
+        let headers = vec![(String::from("Subject"), String::from("Injected\r\nBcc: attacker@test.com"))];
+
+        assert!(Part::text("text/plain", "Hello").into_message(&headers).is_err());
+    }
+}
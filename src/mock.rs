@@ -0,0 +1,392 @@
+//! In-memory transport for unit testing code that uses a [`Client`](../struct.Client.html),
+//! without any HTTP server. Requires the `test-util` feature.
+//!
+//! A client built with a [`MockTransport`](struct.MockTransport.html) sends nothing over the
+//! network. Each request is captured instead, and answered with the next queued
+//! [`MockResponse`](struct.MockResponse.html), so tests can assert on the URL, headers, and form
+//! fields the library sent. Requests pass through the same encoding, logging, and response
+//! parsing as real ones, so errors such as
+//! [`Error::Unauthorized`](../enum.Error.html#variant.Unauthorized) can be tested by queuing the
+//! matching response.
+//!
+//! When no response is queued, requests are answered with `200 OK` and the body MailGun sends
+//! back after queuing a message.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use mailgun_sdk::ClientBuilder;
+//! use mailgun_sdk::message::{self, Email, MessageBuilder};
+//! use mailgun_sdk::mock::{MockResponse, MockTransport};
+//!
+//! let transport = MockTransport::new();
+//! transport.push_response(MockResponse::new(200, r#"{ "id": "<1@YOUR_DOMAIN.com>", "message": "Queued. Thank you." }"#));
+//!
+//! let client = ClientBuilder::new("YOUR_API_KEY", "YOUR_DOMAIN.com")
+//!     .transport(transport.clone())
+//!     .build()
+//!     .unwrap();
+//!
+//! let from = Email::from("from@host.com");
+//! let to = message::emails(&["to@host.com"]);
+//!
+//! let mut builder = MessageBuilder::new("Subject Line", &from, &to);
+//! builder.text(Some("Message Body"));
+//!
+//! client.send_message(builder.get_message()).unwrap();
+//!
+//! let request = transport.last_request().unwrap();
+//!
+//! assert_eq!("https://api.mailgun.net/v3/YOUR_DOMAIN.com/messages", request.url());
+//! assert_eq!(Some(String::from("Subject Line")), request.form_value("subject"));
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Body sent back for requests made while no response is queued.
+const DEFAULT_BODY: &str = r#"{ "id": "<mock@mailgun-sdk>", "message": "Queued. Thank you." }"#;
+
+/// Transport that captures requests and answers them with canned responses.
+///
+/// Clones share the same queue of responses and captured requests, so a clone can be passed to
+/// [`ClientBuilder::transport`](../struct.ClientBuilder.html#method.transport) while the test
+/// keeps the original to make assertions.
+#[derive(Clone, Debug, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    requests: Vec<CapturedRequest>,
+    responses: VecDeque<MockResponse>,
+}
+
+impl MockTransport {
+    /// Create a transport with no queued responses.
+    pub fn new() -> MockTransport {
+        MockTransport::default()
+    }
+
+    /// Queue `response` to answer a request. Responses are used in the order they are queued.
+    pub fn push_response(&self, response: MockResponse) -> &MockTransport {
+        self.state().responses.push_back(response);
+
+        self
+    }
+
+    /// Get every request captured so far, in the order they were sent.
+    pub fn requests(&self) -> Vec<CapturedRequest> {
+        self.state().requests.clone()
+    }
+
+    /// Get the last request captured, if any.
+    pub fn last_request(&self) -> Option<CapturedRequest> {
+        self.state().requests.last().cloned()
+    }
+
+    /// Forget the captured requests and queued responses.
+    pub fn clear(&self) {
+        let mut state = self.state();
+
+        state.requests.clear();
+        state.responses.clear();
+    }
+
+    /// Capture `request`, and return the response to answer it with.
+    pub(crate) fn respond(&self, request: CapturedRequest) -> MockResponse {
+        let mut state = self.state();
+
+        state.requests.push(request);
+        state.responses.pop_front().unwrap_or_else(|| MockResponse::new(200, DEFAULT_BODY))
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+/// Canned response returned by a [`MockTransport`](struct.MockTransport.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockResponse {
+    status: u16,
+    content_type: Option<String>,
+    body: String,
+    pending: bool,
+}
+
+impl MockResponse {
+    /// Create a JSON response with `status` and `body`.
+    pub fn new<B>(status: u16, body: B) -> MockResponse
+    where
+        B: Into<String>,
+    {
+        MockResponse {
+            status,
+            content_type: Some(String::from("application/json")),
+            body: body.into(),
+            pending: false,
+        }
+    }
+
+    /// Create a response that never arrives, to test cancelling a request deterministically.
+    ///
+    /// Asynchronous requests answered with it stay pending until their future is dropped.
+    /// Blocking requests fail with [`Error::Timeout`](../enum.Error.html#variant.Timeout)
+    /// instead of blocking forever.
+    pub fn pending() -> MockResponse {
+        MockResponse { pending: true, ..MockResponse::new(200, DEFAULT_BODY) }
+    }
+
+    /// Set the `Content-Type` of the response, or `None` to leave it out. Defaults to
+    /// `application/json`.
+    pub fn content_type(mut self, content_type: Option<&str>) -> MockResponse {
+        self.content_type = content_type.map(String::from);
+
+        self
+    }
+
+    /// Get the status code of the response.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Get the body of the response.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Check whether the response never arrives.
+    pub(crate) fn is_pending(&self) -> bool {
+        self.pending
+    }
+
+    /// Split the response into the parts checked by the response parsers.
+    pub(crate) fn into_parts(self) -> (reqwest::StatusCode, reqwest::header::HeaderMap, String) {
+        let status = reqwest::StatusCode::from_u16(self.status).unwrap_or(reqwest::StatusCode::INTERNAL_SERVER_ERROR);
+
+        let mut headers = reqwest::header::HeaderMap::new();
+
+        if let Some(content_type) = self.content_type.and_then(|value| value.parse().ok()) {
+            headers.insert(reqwest::header::CONTENT_TYPE, content_type);
+        }
+
+        (status, headers, self.body)
+    }
+}
+
+/// Request captured by a [`MockTransport`](struct.MockTransport.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapturedRequest {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl CapturedRequest {
+    /// Capture a request. The credentials in the `Authorization` header are redacted.
+    pub(crate) fn new(method: &reqwest::Method, url: &str, headers: &reqwest::header::HeaderMap, body: &[u8]) -> CapturedRequest {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| {
+                let value = value.to_str().unwrap_or_default();
+
+                let value = if *name == reqwest::header::AUTHORIZATION {
+                    let scheme = value.split(' ').next().unwrap_or_default();
+
+                    format!("{} {}", scheme, crate::client::REDACTED)
+                } else {
+                    value.to_string()
+                };
+
+                (name.as_str().to_string(), value)
+            })
+            .collect();
+
+        CapturedRequest {
+            method: method.as_str().to_string(),
+            url: url.to_string(),
+            headers,
+            body: body.to_vec(),
+        }
+    }
+
+    /// Get the method of the request, such as `POST`.
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Get the full URL of the request, including the query string.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get the headers of the request. The API key is redacted from the `Authorization` header.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Get the value of the first header named `name`, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Get the raw body of the request.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Decode the form fields of the body, in order. Works with both URL-encoded and
+    /// `multipart/form-data` bodies. File parts, such as attachments, are left out.
+    pub fn form_fields(&self) -> Vec<(String, String)> {
+        let content_type = self.header("Content-Type").unwrap_or_default();
+
+        match content_type.split("boundary=").nth(1) {
+            Some(boundary) if content_type.starts_with("multipart/form-data") => multipart_fields(&self.body, boundary),
+            _ => serde_urlencoded::from_bytes(&self.body).unwrap_or_default(),
+        }
+    }
+
+    /// Get the value of the first form field named `name`.
+    pub fn form_value(&self, name: &str) -> Option<String> {
+        self.form_values(name).into_iter().next()
+    }
+
+    /// Get the values of every form field named `name`, such as each `o:tag`.
+    pub fn form_values(&self, name: &str) -> Vec<String> {
+        self.form_fields()
+            .into_iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value)
+            .collect()
+    }
+}
+
+/// Decode the text fields of a `multipart/form-data` body.
+fn multipart_fields(body: &[u8], boundary: &str) -> Vec<(String, String)> {
+    let body = String::from_utf8_lossy(body);
+    let delimiter = format!("--{}", boundary);
+
+    body.split(delimiter.as_str())
+        .filter_map(|part| {
+            let part = part.strip_prefix("\r\n")?;
+            let (headers, value) = part.split_at(part.find("\r\n\r\n")?);
+
+            if headers.contains("filename=\"") {
+                return None;
+            }
+
+            let name = headers.split("name=\"").nth(1)?;
+            let name = name[..name.find('"')?].to_string();
+
+            let value = &value[4..];
+            let value = value.strip_suffix("\r\n").unwrap_or(value);
+
+            Some((name, value.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod tests {
+    use super::*;
+    use crate::message::{self, Attachment, Email, MessageBuilder};
+    use crate::ClientBuilder;
+
+    #[test]
+    fn mock_transport_captures_message() {
+        let transport = MockTransport::new();
+
+        let client = ClientBuilder::new("key-3ax6xnjp29jd6fds4gc373sgvjxteol0", "test.com")
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let from = Email::from("from@test.com");
+        let to = message::emails(&["test1@test.com", "test2@test.com"]);
+        let attachment = Attachment::new("Cargo.toml", "Cargo.toml");
+
+        let mut builder = MessageBuilder::new("Subject Line", &from, &to);
+        builder.text(Some("Message Body"));
+        builder.add_tag("first").unwrap().add_tag("second").unwrap();
+
+        match client.send_message(builder.get_message()).unwrap() {
+            message::SendMessageResponse::Success { id, .. } => assert_eq!("<mock@mailgun-sdk>", id),
+            other => panic!("Unexpected response: {:?}", other),
+        }
+
+        builder.attachment(&attachment);
+
+        client.send_message(builder.get_message()).unwrap();
+
+        let requests = transport.requests();
+
+        assert_eq!(2, requests.len());
+
+        for request in &requests {
+            assert_eq!("POST", request.method());
+            assert_eq!("https://api.mailgun.net/v3/test.com/messages", request.url());
+            assert_eq!(Some("Basic [REDACTED]"), request.header("authorization"));
+            assert_eq!(Some(String::from("test1@test.com,test2@test.com")), request.form_value("to"));
+            assert_eq!(Some(String::from("Message Body")), request.form_value("text"));
+            assert_eq!(vec![String::from("first"), String::from("second")], request.form_values("o:tag"));
+            assert!(request.form_values("attachment").is_empty());
+        }
+
+        assert!(requests[1].header("content-type").unwrap().starts_with("multipart/form-data"));
+
+        transport.clear();
+
+        assert_eq!(None, transport.last_request());
+    }
+
+    #[test]
+    fn mock_transport_canned_responses() {
+        let transport = MockTransport::new();
+
+        transport
+            .push_response(MockResponse::new(200, r#"{ "limit": 10000, "current": 9950, "period": "1m" }"#))
+            .push_response(MockResponse::new(401, "Forbidden").content_type(None))
+            .push_response(MockResponse::new(502, "<html>Bad Gateway</html>").content_type(Some("text/html")))
+            .push_response(MockResponse::new(200, "<html>Captive Portal</html>").content_type(Some("text/html")))
+            .push_response(MockResponse::pending());
+
+        let client = ClientBuilder::new("key-3ax6xnjp29jd6fds4gc373sgvjxteol0", "test.com")
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(50, client.get_account_usage().unwrap().remaining());
+
+        match client.get_account_usage() {
+            Err(crate::Error::Unauthorized) => {},
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        match client.get_account_usage() {
+            Err(crate::Error::ApiError { status: 502, message }) => assert_eq!("<html>Bad Gateway</html>", message),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        match client.get_account_usage() {
+            Err(crate::Error::UnexpectedResponse { status: 200, .. }) => {},
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        match client.get_account_usage() {
+            Err(crate::Error::Timeout(_)) => {},
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        let request = transport.last_request().unwrap();
+
+        assert_eq!("GET", request.method());
+        assert_eq!("https://api.mailgun.net/v5/accounts/limit/custom/monthly", request.url());
+        assert!(request.form_fields().is_empty());
+    }
+}
@@ -0,0 +1,117 @@
+//! Lazy iteration over the pages of results of paginated APIs.
+
+use crate::error;
+use crate::Client;
+use std::collections::VecDeque;
+
+/// Fetch a page of results, returning its items and the URL of the next page.
+pub(crate) type FetchPage<T> = fn(&Client, reqwest::blocking::RequestBuilder) -> Result<(Vec<T>, String), error::Error<'static>>;
+
+/// Iterator over the items of a paginated API, such as events.
+///
+/// Pages are fetched lazily through the [`Client`](struct.Client.html) the iterator was created
+/// with, only once the items of the previous page have been consumed. The iterator ends after
+/// the last page, or after the first error.
+///
+/// ### Example
+///
+/// ```no_run
+/// use mailgun_sdk::Client as MailGunClient;
+/// use mailgun_sdk::events::EventsQuery;
+///
+/// let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+///
+/// for event in client.iter_events(&EventsQuery::default()) {
+///     println!("{:?}", event.unwrap());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PagedIterator<'c, T> {
+    client: &'c Client<'c>,
+    next: Option<reqwest::blocking::RequestBuilder>,
+    buffer: VecDeque<T>,
+    fetch: FetchPage<T>,
+}
+
+impl<'c, T> PagedIterator<'c, T> {
+    /// Create an iterator starting with the page returned by `first`.
+    pub(crate) fn new(client: &'c Client<'c>, first: reqwest::blocking::RequestBuilder, fetch: FetchPage<T>) -> PagedIterator<'c, T> {
+        PagedIterator {
+            client,
+            next: Some(first),
+            buffer: VecDeque::new(),
+            fetch,
+        }
+    }
+}
+
+impl<'c, T> Iterator for PagedIterator<'c, T> {
+    type Item = Result<T, error::Error<'static>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.buffer.pop_front() {
+                return Some(Ok(item));
+            }
+
+            let request = self.next.take()?;
+
+            match (self.fetch)(self.client, request) {
+                Ok((items, next)) => {
+                    if items.is_empty() {
+                        return None;
+                    }
+
+                    self.buffer.extend(items);
+                    self.next = Some(self.client.request(reqwest::Method::GET, &next));
+                },
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve three pages of numbers from the `page` query parameter, without any requests.
+    fn fetch_numbers(_: &Client, request: reqwest::blocking::RequestBuilder) -> Result<(Vec<u32>, String), error::Error<'static>> {
+        let request = request.build().unwrap();
+        let page = request.url()
+            .query_pairs()
+            .find(|(key, _)| key == "page")
+            .map(|(_, value)| value.parse::<u32>().unwrap())
+            .unwrap();
+
+        match page {
+            1 => Ok((vec![1, 2], String::from("https://api.mailgun.net/numbers?page=2"))),
+            2 => Ok((vec![3], String::from("https://api.mailgun.net/numbers?page=3"))),
+            3 => Ok((vec![], String::from("https://api.mailgun.net/numbers?page=4"))),
+            _ => Err(error::Error::Unknown(String::from("Fetched past the last page"))),
+        }
+    }
+
+    #[test]
+    fn paged_iterator_follows_pages() {
+        let client = Client::new("api_key", "domain");
+        let first = client.request(reqwest::Method::GET, "https://api.mailgun.net/numbers?page=1");
+
+        let numbers = PagedIterator::new(&client, first, fetch_numbers)
+            .collect::<Result<Vec<u32>, _>>()
+            .unwrap();
+
+        assert_eq!(vec![1, 2, 3], numbers);
+    }
+
+    #[test]
+    fn paged_iterator_stops_after_error() {
+        let client = Client::new("api_key", "domain");
+        let first = client.request(reqwest::Method::GET, "https://api.mailgun.net/numbers?page=5");
+
+        let mut iterator = PagedIterator::new(&client, first, fetch_numbers);
+
+        assert!(iterator.next().unwrap().is_err());
+        assert!(iterator.next().is_none());
+    }
+}
@@ -0,0 +1,217 @@
+//! Client-side pacing of requests, to stay below the rate limits of MailGun.
+
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "async")]
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Token bucket limiting how many requests are sent per second.
+///
+/// The bucket holds up to `per_second` tokens and refills continuously, so short bursts of up
+/// to `per_second` requests go out immediately, and sustained traffic is paced to `per_second`
+/// requests per second. Clones share the same bucket, so every clone of a
+/// [`Client`](struct.Client.html) counts towards the same limit.
+///
+/// Set one up with [`ClientBuilder::rate_limit`](struct.ClientBuilder.html#method.rate_limit).
+///
+/// ### Example
+///
+/// ```rust
+/// use mailgun_sdk::RateLimiter;
+///
+/// let limiter = RateLimiter::new(2);
+///
+/// assert!(limiter.try_acquire().is_ok());
+/// assert!(limiter.try_acquire().is_ok());
+/// assert!(limiter.try_acquire().is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    per_second: u32,
+    state: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// Create a limiter allowing `per_second` requests per second, starting with a full bucket.
+    ///
+    /// Panics if `per_second` is `0`.
+    pub fn new(per_second: u32) -> RateLimiter {
+        assert!(per_second > 0, "The rate limit must allow at least one request per second");
+
+        RateLimiter {
+            per_second,
+            state: Arc::new(Mutex::new(Bucket { tokens: f64::from(per_second), updated: Instant::now() })),
+        }
+    }
+
+    /// Get the number of requests allowed per second.
+    pub fn per_second(&self) -> u32 {
+        self.per_second
+    }
+
+    /// Take a token if one is available. Otherwise, return how long to wait before trying
+    /// again, without blocking.
+    ///
+    /// Useful to pace requests sent outside of the client with the timer of the runtime, since
+    /// the client only paces its own requests.
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    /// Block the current thread until a token is available, then take it.
+    pub fn acquire(&self) {
+        while let Err(wait) = self.try_acquire() {
+            thread::sleep(wait);
+        }
+    }
+
+    /// Wait until a token is available, then take it, without blocking the thread polling the
+    /// future.
+    #[cfg(feature = "async")]
+    pub(crate) async fn acquire_async(&self) {
+        while let Err(wait) = self.try_acquire() {
+            Delay::new(wait).await;
+        }
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> Result<(), Duration> {
+        let mut bucket = self.state.lock().unwrap_or_else(|error| error.into_inner());
+
+        let rate = f64::from(self.per_second);
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(rate);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+}
+
+/// Future completing once a duration has elapsed. The duration is timed on a helper thread, so
+/// the future works with any runtime.
+#[cfg(feature = "async")]
+struct Delay {
+    duration: Duration,
+    state: Option<Arc<Mutex<DelayState>>>,
+}
+
+#[cfg(feature = "async")]
+#[derive(Default)]
+struct DelayState {
+    elapsed: bool,
+    waker: Option<Waker>,
+}
+
+#[cfg(feature = "async")]
+impl Delay {
+    fn new(duration: Duration) -> Delay {
+        Delay { duration, state: None }
+    }
+}
+
+#[cfg(feature = "async")]
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let duration = self.duration;
+
+        let state = self.state.get_or_insert_with(|| {
+            let state = Arc::new(Mutex::new(DelayState::default()));
+            let timer = Arc::clone(&state);
+
+            thread::spawn(move || {
+                thread::sleep(duration);
+
+                let mut timer = timer.lock().unwrap_or_else(|error| error.into_inner());
+                timer.elapsed = true;
+
+                if let Some(waker) = timer.waker.take() {
+                    waker.wake();
+                }
+            });
+
+            state
+        });
+
+        let mut state = state.lock().unwrap_or_else(|error| error.into_inner());
+
+        if state.elapsed {
+            Poll::Ready(())
+        } else {
+            state.waker = Some(cx.waker().clone());
+
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limiter_token_bucket() {
+        let limiter = RateLimiter::new(4);
+        let start = Instant::now();
+
+        for _ in 0..4 {
+            assert_eq!(Ok(()), limiter.try_acquire_at(start));
+        }
+
+        assert_eq!(Err(Duration::from_millis(250)), limiter.try_acquire_at(start));
+        assert_eq!(Err(Duration::from_millis(125)), limiter.try_acquire_at(start + Duration::from_millis(125)));
+        assert_eq!(Ok(()), limiter.try_acquire_at(start + Duration::from_millis(250)));
+
+        let clone = limiter.clone();
+
+        assert!(clone.try_acquire_at(start + Duration::from_millis(250)).is_err());
+        assert_eq!(Ok(()), limiter.try_acquire_at(start + Duration::from_millis(500)));
+
+        for _ in 0..4 {
+            assert_eq!(Ok(()), clone.try_acquire_at(start + Duration::from_secs(10)));
+        }
+
+        assert!(clone.try_acquire_at(start + Duration::from_secs(10)).is_err());
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn rate_limiter_acquire_async() {
+        let limiter = RateLimiter::new(10);
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+        let started = Instant::now();
+
+        runtime.block_on(async {
+            for _ in 0..12 {
+                limiter.acquire_async().await;
+            }
+        });
+
+        assert!(started.elapsed() >= Duration::from_millis(150), "{:?}", started.elapsed());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rate_limiter_zero() {
+        RateLimiter::new(0);
+    }
+}
@@ -0,0 +1,116 @@
+//! Model for subaccounts managed by a primary MailGun account.
+//!
+//! Full API documentation: [https://documentation.mailgun.com/en/latest/subaccounts.html](https://documentation.mailgun.com/en/latest/subaccounts.html)
+//!
+//! Subaccounts are separate MailGun accounts managed by a primary account, typically used by
+//! agencies to manage their customers' sending. Note that the Subaccounts API lives under the
+//! `/v5` base path, rather than `/v3` like most of the other APIs.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "blocking")] {
+//! use mailgun_sdk::Client as MailGunClient;
+//!
+//! let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+//!
+//! for subaccount in client.list_subaccounts().unwrap() {
+//!     println!("{} ({}): {}", subaccount.name, subaccount.id, subaccount.status);
+//! }
+//! # }
+//! ```
+
+#[cfg(feature = "blocking")]
+use crate::error;
+#[cfg(feature = "blocking")]
+use crate::util::path_segment;
+use serde::Deserialize;
+
+/// A subaccount managed by the primary account.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Subaccount {
+    /// Unique identifier of the subaccount.
+    pub id: String,
+
+    /// Display name of the subaccount.
+    pub name: String,
+
+    /// Status of the subaccount, such as `open` or `disabled`.
+    pub status: String,
+}
+
+/// Response sent back from MailGun when listing subaccounts.
+#[cfg(feature = "blocking")]
+#[derive(Debug, Deserialize)]
+struct ListSubaccountsResponse {
+    subaccounts: Vec<Subaccount>,
+}
+
+/// Response sent back from MailGun when fetching a single subaccount.
+#[cfg(feature = "blocking")]
+#[derive(Debug, Deserialize)]
+struct GetSubaccountResponse {
+    subaccount: Subaccount,
+}
+
+/// List the subaccounts managed by the account of an existing [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn list_subaccounts_with_client(client: &crate::Client) -> Result<Vec<Subaccount>, error::Error<'static>> {
+    let url = client.url_for("/v5/accounts/subaccounts");
+
+    let request = client.request(reqwest::Method::GET, &url);
+
+    client
+        .send_request::<ListSubaccountsResponse>(request)
+        .map(|response| response.subaccounts)
+}
+
+/// Get a single subaccount by its `id` with an existing [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn get_subaccount_with_client(client: &crate::Client, id: &str) -> Result<Subaccount, error::Error<'static>> {
+    let url = client.url_for(&format!("/v5/accounts/subaccounts/{}", path_segment(id)));
+
+    let request = client.request(reqwest::Method::GET, &url);
+
+    client
+        .send_request::<GetSubaccountResponse>(request)
+        .map(|response| response.subaccount)
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_subaccounts_response_deserialize() {
+        let body = r#"{
+            "subaccounts": [
+                { "id": "646d00a1b32c35364a2ad34f", "name": "test_subaccount", "status": "open" },
+                { "id": "646d00a1b32c35364a2ad350", "name": "other_subaccount", "status": "disabled" }
+            ],
+            "total": 2
+        }"#;
+
+        let response = serde_json::from_str::<ListSubaccountsResponse>(body).unwrap();
+
+        assert_eq!(2, response.subaccounts.len());
+        assert_eq!("646d00a1b32c35364a2ad34f", response.subaccounts[0].id);
+        assert_eq!("test_subaccount", response.subaccounts[0].name);
+        assert_eq!("disabled", response.subaccounts[1].status);
+    }
+
+    #[test]
+    fn get_subaccount_response_deserialize() {
+        let body = r#"{
+            "subaccount": { "id": "646d00a1b32c35364a2ad34f", "name": "test_subaccount", "status": "open" }
+        }"#;
+
+        let response = serde_json::from_str::<GetSubaccountResponse>(body).unwrap();
+
+        assert_eq!(Subaccount {
+            id: String::from("646d00a1b32c35364a2ad34f"),
+            name: String::from("test_subaccount"),
+            status: String::from("open"),
+        }, response.subaccount);
+    }
+}
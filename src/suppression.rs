@@ -0,0 +1,195 @@
+//! Model for the suppression lists of a domain, which hold the addresses MailGun will not send
+//! to: bounces, unsubscribes, and complaints.
+//!
+//! Full API documentation: [https://documentation.mailgun.com/en/latest/api-suppressions.html](https://documentation.mailgun.com/en/latest/api-suppressions.html)
+//!
+//! MailGun drops messages to suppressed addresses, and sending to them anyway hurts the
+//! reputation of the domain. Checking an address before sending avoids both.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "blocking")] {
+//! use mailgun_sdk::Client as MailGunClient;
+//!
+//! let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+//!
+//! let status = client.is_suppressed("recipient@domain.com").unwrap();
+//!
+//! if status.is_suppressed() {
+//!     println!("Skipping recipient, found in {:?}", status.lists());
+//! }
+//! # }
+//! ```
+
+#[cfg(feature = "blocking")]
+use crate::error;
+#[cfg(feature = "blocking")]
+use crate::util::path_segment;
+
+/// Suppression list of a domain.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SuppressionList {
+    /// Addresses that bounced.
+    Bounces,
+
+    /// Addresses that unsubscribed.
+    Unsubscribes,
+
+    /// Addresses that marked a message as spam.
+    Complaints,
+}
+
+impl SuppressionList {
+    /// Every suppression list, in the order they are checked.
+    pub const ALL: [SuppressionList; 3] = [SuppressionList::Bounces, SuppressionList::Unsubscribes, SuppressionList::Complaints];
+
+    /// Name of the list in the API path, such as `bounces`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SuppressionList::Bounces => "bounces",
+            SuppressionList::Unsubscribes => "unsubscribes",
+            SuppressionList::Complaints => "complaints",
+        }
+    }
+}
+
+/// Which suppression lists of the domain an address is on.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SuppressionStatus {
+    /// Whether the address is on the bounces list.
+    pub bounced: bool,
+
+    /// Whether the address is on the unsubscribes list.
+    pub unsubscribed: bool,
+
+    /// Whether the address is on the complaints list.
+    pub complained: bool,
+}
+
+impl SuppressionStatus {
+    /// Whether the address is on any suppression list.
+    pub fn is_suppressed(&self) -> bool {
+        self.bounced || self.unsubscribed || self.complained
+    }
+
+    /// Return the suppression lists the address is on.
+    pub fn lists(&self) -> Vec<SuppressionList> {
+        SuppressionList::ALL
+            .iter()
+            .copied()
+            .filter(|list| self.contains(*list))
+            .collect()
+    }
+
+    /// Whether the address is on `list`.
+    pub fn contains(&self, list: SuppressionList) -> bool {
+        match list {
+            SuppressionList::Bounces => self.bounced,
+            SuppressionList::Unsubscribes => self.unsubscribed,
+            SuppressionList::Complaints => self.complained,
+        }
+    }
+}
+
+/// Check whether `address` is on a suppression list of the domain of an existing
+/// [`Client`](../struct.Client.html).
+///
+/// Sends one request per list, and stops at the first error.
+#[cfg(feature = "blocking")]
+pub fn is_suppressed_with_client(client: &crate::Client, address: &str) -> Result<SuppressionStatus, error::Error<'static>> {
+    Ok(SuppressionStatus {
+        bounced: is_listed_with_client(client, SuppressionList::Bounces, address)?,
+        unsubscribed: is_listed_with_client(client, SuppressionList::Unsubscribes, address)?,
+        complained: is_listed_with_client(client, SuppressionList::Complaints, address)?,
+    })
+}
+
+/// Check whether `address` is on `list` with an existing [`Client`](../struct.Client.html).
+#[cfg(feature = "blocking")]
+pub fn is_listed_with_client(client: &crate::Client, list: SuppressionList, address: &str) -> Result<bool, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/{}/{}/{}", client.domain(), list.as_str(), path_segment(address)));
+
+    let request = client.request(reqwest::Method::GET, &url);
+
+    listed(client.send_request::<serde_json::Value>(request))
+}
+
+/// Map the result of fetching an address from a suppression list, where "not found" means the
+/// address is not on the list.
+#[cfg(feature = "blocking")]
+fn listed(result: Result<serde_json::Value, error::Error<'static>>) -> Result<bool, error::Error<'static>> {
+    match result {
+        Ok(_) => Ok(true),
+        Err(error::Error::ApiError { status: 404, .. }) => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suppression_status_lists() {
+        let status = SuppressionStatus::default();
+
+        assert!(!status.is_suppressed());
+        assert!(status.lists().is_empty());
+
+        let status = SuppressionStatus { bounced: true, complained: true, ..Default::default() };
+
+        assert!(status.is_suppressed());
+        assert_eq!(vec![SuppressionList::Bounces, SuppressionList::Complaints], status.lists());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn listed_from_result() {
+        let found = serde_json::json!({ "address": "test@test.com", "code": "550", "error": "No such mailbox" });
+
+        assert!(listed(Ok(found)).unwrap());
+        assert!(!listed(Err(error::Error::ApiError { status: 404, message: String::from("Address not found in bounces table") })).unwrap());
+
+        match listed(Err(error::Error::Unauthorized)) {
+            Err(error::Error::Unauthorized) => {},
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn is_listed_html_not_found() {
+        use crate::mock::{MockResponse, MockTransport};
+
+        let transport = MockTransport::new();
+        transport.push_response(MockResponse::new(404, "<html>Not Found</html>").content_type(Some("text/html")));
+
+        let client = crate::ClientBuilder::new("api_key", "test.com")
+            .transport(transport)
+            .build()
+            .unwrap();
+
+        assert!(!is_listed_with_client(&client, SuppressionList::Bounces, "test@test.com").unwrap());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn is_listed_encodes_address() {
+        use crate::mock::MockTransport;
+
+        let transport = MockTransport::new();
+
+        let client = crate::ClientBuilder::new("api_key", "test.com")
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        is_listed_with_client(&client, SuppressionList::Unsubscribes, "../../domains?limit=1#test@test.com").unwrap();
+
+        assert_eq!(
+            "https://api.mailgun.net/v3/test.com/unsubscribes/..%2F..%2Fdomains%3Flimit%3D1%23test@test.com",
+            transport.last_request().unwrap().url(),
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Helpers for applications built on top of the library.
+
+/// Compare two strings in constant time, such as a shared secret received in a request header
+/// against the configured value, or the signature of a
+/// [`WebhookSignature`](../webhook/struct.WebhookSignature.html) against the one computed by
+/// the application.
+///
+/// The time taken depends only on the length of the longest string, never on where the strings
+/// first differ, so an attacker cannot guess a secret one character at a time by timing the
+/// comparison.
+///
+/// ### Example
+///
+/// ```rust
+/// use mailgun_sdk::util::secure_compare;
+///
+/// assert!(secure_compare("d2271d12299f", "d2271d12299f"));
+/// assert!(!secure_compare("d2271d12299f", "d2271d12299e"));
+/// ```
+#[must_use]
+pub fn secure_compare(a: &str, b: &str) -> bool {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+
+    let mut diff = a.len() ^ b.len();
+
+    for i in 0..a.len().max(b.len()) {
+        let left = a.get(i).copied().unwrap_or(0);
+        let right = b.get(i).copied().unwrap_or(0);
+
+        diff |= usize::from(left ^ right);
+    }
+
+    diff == 0
+}
+
+/// Convert a boolean into the `yes`/`no` form expected by MailGun.
+pub(crate) fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+/// Percent-encode `segment` for use as a single segment of a URL path, so that a value such as
+/// an address or a login containing `/`, `?`, or `#` cannot change the endpoint of a request.
+#[cfg(feature = "blocking")]
+pub(crate) fn path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'@' => encoded.push(char::from(byte)),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secure_compare_strings() {
+        assert!(secure_compare("", ""));
+        assert!(secure_compare("key-3ax6xnjp29jd6fds4gc373sgvjxteol0", "key-3ax6xnjp29jd6fds4gc373sgvjxteol0"));
+
+        assert!(!secure_compare("key-3ax6", "key-3ax7"));
+        assert!(!secure_compare("key", "key\0"));
+        assert!(!secure_compare("", "key"));
+        assert!(!secure_compare("Key", "key"));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn path_segment_encoding() {
+        assert_eq!("test@test.com", path_segment("test@test.com"));
+        assert_eq!("first.last%2Btag@test.com", path_segment("first.last+tag@test.com"));
+        assert_eq!("..%2F..%2Fdomains%3Fa%3Db%23c", path_segment("../../domains?a=b#c"));
+        assert_eq!("%20%C3%A9%25", path_segment(" é%"));
+    }
+}
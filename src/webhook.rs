@@ -0,0 +1,354 @@
+//! Model for the webhooks of a domain.
+//!
+//! Full API documentation: [https://documentation.mailgun.com/en/latest/api-webhooks.html](https://documentation.mailgun.com/en/latest/api-webhooks.html)
+//!
+//! Webhooks are URLs MailGun calls when an event happens to a message sent from the domain,
+//! with one webhook per [`WebhookKind`](enum.WebhookKind.html).
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "blocking")] {
+//! use mailgun_sdk::Client as MailGunClient;
+//! use mailgun_sdk::webhook::WebhookKind;
+//!
+//! let client = MailGunClient::new("YOUR_API_KEY", "YOUR_DOMAIN.com");
+//!
+//! let response = client.test_webhook(WebhookKind::Delivered).unwrap();
+//!
+//! println!("{:?}: {}", response.code, response.message);
+//! # }
+//! ```
+//!
+//! The JSON body MailGun posts to a webhook can be deserialized into a
+//! [`WebhookPayload`](struct.WebhookPayload.html).
+//!
+//! ```rust
+//! use mailgun_sdk::webhook::{WebhookKind, WebhookPayload};
+//!
+//! let body = r#"{
+//!     "signature": { "timestamp": "1529006854", "token": "a8ce0edb2dd8", "signature": "d2271d12299f" },
+//!     "event-data": { "event": "delivered", "id": "CPgfbmQMTCKtHW6uIWtuVe", "timestamp": 1521472262.908181 }
+//! }"#;
+//!
+//! let payload = serde_json::from_str::<WebhookPayload>(body).unwrap();
+//!
+//! assert_eq!(Some(WebhookKind::Delivered), payload.event_data.kind());
+//! ```
+
+#[cfg(feature = "blocking")]
+use crate::error;
+use crate::events::EventMessage;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Kind of event a webhook is called for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WebhookKind {
+    /// MailGun accepted a message for delivery.
+    Accepted,
+
+    /// A recipient clicked a tracked link.
+    Clicked,
+
+    /// A recipient marked a message as spam.
+    Complained,
+
+    /// A message was delivered.
+    Delivered,
+
+    /// A recipient opened a message.
+    Opened,
+
+    /// Delivery of a message failed permanently.
+    PermanentFail,
+
+    /// Delivery of a message failed temporarily and will be retried.
+    TemporaryFail,
+
+    /// A recipient unsubscribed.
+    Unsubscribed,
+}
+
+impl WebhookKind {
+    /// Name of the webhook in the MailGun API, such as `delivered` or `permanent_fail`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebhookKind::Accepted => "accepted",
+            WebhookKind::Clicked => "clicked",
+            WebhookKind::Complained => "complained",
+            WebhookKind::Delivered => "delivered",
+            WebhookKind::Opened => "opened",
+            WebhookKind::PermanentFail => "permanent_fail",
+            WebhookKind::TemporaryFail => "temporary_fail",
+            WebhookKind::Unsubscribed => "unsubscribed",
+        }
+    }
+}
+
+impl fmt::Display for WebhookKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Body MailGun posts to a webhook, in the format of version 3 of the webhooks.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct WebhookPayload {
+    /// Signature proving the payload was sent by MailGun.
+    pub signature: WebhookSignature,
+
+    /// The event that triggered the webhook.
+    #[serde(rename = "event-data")]
+    pub event_data: EventData,
+}
+
+/// Signature block of a [`WebhookPayload`](struct.WebhookPayload.html).
+///
+/// The `signature` is the HMAC-SHA256 of the `timestamp` followed by the `token`, keyed with
+/// the webhook signing key of the account. Compare it to the signature computed by the
+/// application with [`util::secure_compare`](../util/fn.secure_compare.html).
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct WebhookSignature {
+    /// Time the payload was signed, as a Unix timestamp.
+    pub timestamp: String,
+
+    /// Random string used once per payload.
+    pub token: String,
+
+    /// Hex-encoded signature of the `timestamp` and `token`.
+    pub signature: String,
+}
+
+/// The event that triggered a webhook.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct EventData {
+    /// Unique identifier of the event.
+    pub id: String,
+
+    /// Type of the event, such as `delivered`, `failed`, or `clicked`.
+    pub event: String,
+
+    /// Time the event happened, as a Unix timestamp.
+    pub timestamp: f64,
+
+    /// Severity of the event, such as `info`, `warn`, or `error`.
+    #[serde(rename = "log-level", default)]
+    pub log_level: Option<String>,
+
+    /// Recipient the event relates to.
+    #[serde(default)]
+    pub recipient: Option<String>,
+
+    /// Tags of the message.
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// For `failed` events, whether the failure is `temporary` or `permanent`.
+    #[serde(default)]
+    pub severity: Option<String>,
+
+    /// For `failed` events, the reason of the failure.
+    #[serde(default)]
+    pub reason: Option<String>,
+
+    /// For `delivered` and `failed` events, the response of the receiving server.
+    #[serde(rename = "delivery-status", default)]
+    pub delivery_status: Option<DeliveryStatus>,
+
+    /// For `clicked` events, the URL that was clicked.
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// For `opened`, `clicked`, `unsubscribed`, and `complained` events, the IP address of
+    /// the recipient.
+    #[serde(default)]
+    pub ip: Option<String>,
+
+    /// Custom variables attached to the message with `v:` fields.
+    #[serde(rename = "user-variables", default)]
+    pub user_variables: HashMap<String, serde_json::Value>,
+
+    /// Message the event relates to.
+    #[serde(default)]
+    pub message: Option<EventMessage>,
+}
+
+impl EventData {
+    /// Kind of webhook the event is sent to, or `None` for events without a webhook.
+    ///
+    /// `failed` events are split into permanent and temporary failures using their
+    /// `severity`.
+    pub fn kind(&self) -> Option<WebhookKind> {
+        match self.event.as_str() {
+            "accepted" => Some(WebhookKind::Accepted),
+            "clicked" => Some(WebhookKind::Clicked),
+            "complained" => Some(WebhookKind::Complained),
+            "delivered" => Some(WebhookKind::Delivered),
+            "opened" => Some(WebhookKind::Opened),
+            "unsubscribed" => Some(WebhookKind::Unsubscribed),
+            "failed" => match self.severity.as_deref() {
+                Some("temporary") => Some(WebhookKind::TemporaryFail),
+                _ => Some(WebhookKind::PermanentFail),
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Response of the receiving server to a delivery attempt.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct DeliveryStatus {
+    /// SMTP status code of the response.
+    #[serde(default)]
+    pub code: Option<u16>,
+
+    /// SMTP message of the response.
+    #[serde(default)]
+    pub message: Option<String>,
+
+    /// Longer description of the response, when MailGun provides one.
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Number of the delivery attempt.
+    #[serde(rename = "attempt-no", default)]
+    pub attempt_no: Option<u32>,
+}
+
+/// Result of sending a test event to a webhook.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct WebhookTestResponse {
+    /// Status code the webhook URL responded with, if it could be reached.
+    #[serde(default, deserialize_with = "deserialize_code")]
+    pub code: Option<u16>,
+
+    /// Response body of the webhook URL, or the reason it could not be reached.
+    pub message: String,
+}
+
+/// MailGun reports the status code of the webhook either as a number or as a string.
+fn deserialize_code<'de, D>(deserializer: D) -> Result<Option<u16>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Code {
+        Number(u16),
+        Text(String),
+    }
+
+    match Option::<Code>::deserialize(deserializer)? {
+        Some(Code::Number(code)) => Ok(Some(code)),
+        Some(Code::Text(code)) => Ok(code.parse().ok()),
+        None => Ok(None),
+    }
+}
+
+/// Send a test event to the webhook of `kind` of the domain of an existing
+/// [`Client`](../struct.Client.html), and return how the webhook URL responded.
+#[cfg(feature = "blocking")]
+pub fn test_webhook_with_client(client: &crate::Client, kind: WebhookKind) -> Result<WebhookTestResponse, error::Error<'static>> {
+    let url = client.url_for(&format!("/v3/domains/{}/webhooks/{}/test", client.domain(), kind));
+
+    let request = client.request(reqwest::Method::PUT, &url);
+
+    client.send_request(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn webhook_test_response_deserialize() {
+        let response = serde_json::from_str::<WebhookTestResponse>(r#"{ "code": "200", "message": "OK" }"#).unwrap();
+        assert_eq!(WebhookTestResponse { code: Some(200), message: String::from("OK") }, response);
+
+        let response = serde_json::from_str::<WebhookTestResponse>(r#"{ "code": 500, "message": "Internal Server Error" }"#).unwrap();
+        assert_eq!(Some(500), response.code);
+
+        let response = serde_json::from_str::<WebhookTestResponse>(r#"{ "message": "connection refused" }"#).unwrap();
+        assert_eq!(None, response.code);
+    }
+
+    #[test]
+    fn webhook_payload_deserialize() {
+        let body = r#"{
+            "signature": {
+                "timestamp": "1529006854",
+                "token": "a8ce0edb2dd8301dee6c2405235584e45aa91d1e9f979f3de0",
+                "signature": "d2271d12299f6592d9d44cd9d250f0704e4674c30d79d07c47a66f95ce71cf55"
+            },
+            "event-data": {
+                "event": "failed",
+                "id": "G9Bn5sl1TC6nu79C8C0bwg",
+                "timestamp": 1521233195.375624,
+                "log-level": "error",
+                "severity": "permanent",
+                "reason": "suppress-bounce",
+                "recipient": "alice@example.com",
+                "tags": ["my_tag_1"],
+                "user-variables": { "my_var_1": "Mailgun Variable #1", "my-var-2": 2 },
+                "delivery-status": {
+                    "attempt-no": 1,
+                    "message": "No Such User Here",
+                    "code": 550,
+                    "description": "",
+                    "session-seconds": 0.097
+                },
+                "message": {
+                    "headers": {
+                        "to": "Alice <alice@example.com>",
+                        "message-id": "20130503192659.13651.20287@example.com",
+                        "from": "Bob <bob@example.com>",
+                        "subject": "Test permanent_fail webhook"
+                    },
+                    "attachments": [],
+                    "size": 111
+                }
+            }
+        }"#;
+
+        let payload = serde_json::from_str::<WebhookPayload>(body).unwrap();
+
+        assert_eq!("1529006854", payload.signature.timestamp);
+        assert_eq!(Some(WebhookKind::PermanentFail), payload.event_data.kind());
+        assert_eq!(Some(550), payload.event_data.delivery_status.as_ref().unwrap().code);
+        assert_eq!(Some(&serde_json::json!(2)), payload.event_data.user_variables.get("my-var-2"));
+        assert_eq!(
+            Some(String::from("20130503192659.13651.20287@example.com")),
+            payload.event_data.message.unwrap().headers.message_id
+        );
+    }
+
+    #[test]
+    fn event_data_kind() {
+        let events = [
+            (r#"{ "event": "delivered", "id": "1", "timestamp": 1.0 }"#, Some(WebhookKind::Delivered)),
+            (r#"{ "event": "failed", "id": "1", "timestamp": 1.0, "severity": "temporary" }"#, Some(WebhookKind::TemporaryFail)),
+            (r#"{ "event": "failed", "id": "1", "timestamp": 1.0, "severity": "permanent" }"#, Some(WebhookKind::PermanentFail)),
+            (r#"{ "event": "opened", "id": "1", "timestamp": 1.0, "ip": "50.56.129.169" }"#, Some(WebhookKind::Opened)),
+            (r#"{ "event": "clicked", "id": "1", "timestamp": 1.0, "url": "https://example.com" }"#, Some(WebhookKind::Clicked)),
+            (r#"{ "event": "unsubscribed", "id": "1", "timestamp": 1.0 }"#, Some(WebhookKind::Unsubscribed)),
+            (r#"{ "event": "complained", "id": "1", "timestamp": 1.0 }"#, Some(WebhookKind::Complained)),
+            (r#"{ "event": "stored", "id": "1", "timestamp": 1.0 }"#, None),
+        ];
+
+        for (body, kind) in events.iter() {
+            let event_data = serde_json::from_str::<EventData>(body).unwrap();
+            assert_eq!(*kind, event_data.kind(), "{}", body);
+        }
+
+        let clicked = serde_json::from_str::<EventData>(events[4].0).unwrap();
+        assert_eq!(Some(String::from("https://example.com")), clicked.url);
+    }
+
+    #[test]
+    fn webhook_kind_as_str() {
+        assert_eq!("permanent_fail", WebhookKind::PermanentFail.as_str());
+        assert_eq!("delivered", WebhookKind::Delivered.to_string());
+    }
+}